@@ -0,0 +1,77 @@
+// Benchmark the 3D attribute updates of many audio sources
+// Compares 10k static sources, which cost no FFI calls, with 10k moving sources
+
+use bevy::prelude::*;
+use bevy_fmod::components::AudioSource;
+use bevy_fmod::{FmodPlugin, FmodStudio};
+use criterion::{Criterion, criterion_group, criterion_main};
+use libfmod::StopMode;
+
+const SOURCE_COUNT: usize = 10_000;
+
+#[derive(Component)]
+struct Moving;
+
+fn create_app(moving: bool) -> Option<App> {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_systems(Update, move_sources);
+
+    let Ok(event_description) = app
+        .world()
+        .resource::<FmodStudio>()
+        .get_event("event:/test")
+    else {
+        println!("Skipping benchmark - no valid events in banks");
+        return None;
+    };
+
+    for i in 0..SOURCE_COUNT {
+        let mut entity = app.world_mut().spawn((
+            AudioSource {
+                event_instance: event_description
+                    .create_instance()
+                    .expect("Failed to create instance"),
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            Transform::from_xyz(i as f32, 0.0, 0.0),
+        ));
+
+        if moving {
+            entity.insert(Moving);
+        }
+    }
+
+    // Push the initial attributes of all sources
+    app.update();
+
+    Some(app)
+}
+
+fn move_sources(mut query: Query<&mut Transform, With<Moving>>) {
+    for mut transform in &mut query {
+        transform.translation.y += 0.01;
+    }
+}
+
+fn bench_sources(c: &mut Criterion) {
+    let mut group = c.benchmark_group("10k audio sources");
+
+    for (name, moving) in [("static", false), ("moving", true)] {
+        let Some(mut app) = create_app(moving) else {
+            return;
+        };
+
+        group.bench_function(name, |b| b.iter(|| app.update()));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sources);
+criterion_main!(benches);
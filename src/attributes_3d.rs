@@ -1,11 +1,21 @@
 use bevy::prelude::Vec3;
 use libfmod::{Attributes3d, Vector};
 
+use crate::spatial_settings::SpatialSettings;
+
 /// Returns the corresponding Attributes3d, which contains all the spatial information FMOD needs
-pub fn attributes3d(pos: Vec3, vel: Vec3, fwd: Vec3, up: Vec3) -> Attributes3d {
+pub fn attributes3d(
+    settings: &SpatialSettings,
+    pos: Vec3,
+    vel: Vec3,
+    fwd: Vec3,
+    up: Vec3,
+) -> Attributes3d {
+    let (fwd, up) = settings.map_orientation(fwd, up);
+
     Attributes3d {
-        position: to_fmod_vec(pos),
-        velocity: to_fmod_vec(vel),
+        position: to_fmod_vec(settings.map_vector(pos)),
+        velocity: to_fmod_vec(settings.map_vector(vel)),
         forward: to_fmod_vec(fwd),
         up: to_fmod_vec(up),
     }
@@ -13,7 +23,8 @@ pub fn attributes3d(pos: Vec3, vel: Vec3, fwd: Vec3, up: Vec3) -> Attributes3d {
 
 /// Takes a vector from Bevy coordinate system into the FMOD coordinate system.
 /// If FMOD_INIT_3D_RIGHTHANDED is enabled then this is a one-to-one conversion.
-fn to_fmod_vec(bevy_vec: Vec3) -> Vector {
+/// Custom axes and 2D projections are applied beforehand through [SpatialSettings].
+pub(crate) fn to_fmod_vec(bevy_vec: Vec3) -> Vector {
     Vector {
         x: bevy_vec.x,
         y: bevy_vec.y,
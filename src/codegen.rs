@@ -0,0 +1,415 @@
+//! Generates typed identifiers for FMOD Studio events, snapshots, buses, VCAs and global
+//! parameters.
+//!
+//! Paths like `"event:/Ambience/Forest"` are only checked by FMOD at runtime. This module reads the
+//! `GUIDs.txt` file that FMOD Studio exports (`File > Export GUIDs...`) and emits a Rust source
+//! file with one constant per object, so typos become compile errors.
+//!
+//! Folder and object names become `snake_case` modules and `SCREAMING_SNAKE_CASE` constants.
+//! Non-ASCII letters are kept, since Rust allows Unicode identifiers. Names without any letters or
+//! digits get a stable `_` plus hash of the name. If two paths end up with the same identifier,
+//! e.g. `Country Side` and `country-side`, generating fails with
+//! [CodegenError::DuplicateIdentifier] instead of dropping one of them.
+//!
+//! ## Usage
+//!
+//! Add `bevy_fmod` as a build dependency and generate the identifiers in your `build.rs`:
+//!
+//! ```no_run
+//! use bevy_fmod::codegen::Codegen;
+//!
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! println!("cargo:rerun-if-changed=assets/audio/demo_project/GUIDs.txt");
+//!
+//! Codegen::from_guids_file("assets/audio/demo_project/GUIDs.txt")
+//!     .unwrap()
+//!     .write_to(format!("{out_dir}/fmod_ids.rs"))
+//!     .unwrap();
+//! ```
+//!
+//! Then include the generated file in your crate:
+//!
+//! ```ignore
+//! mod fmod_ids {
+//!     include!(concat!(env!("OUT_DIR"), "/fmod_ids.rs"));
+//! }
+//!
+//! fn play_forest(studio: Res<FmodStudio>) {
+//!     let description = fmod_ids::events::ambience::FOREST.get(&studio).unwrap();
+//!     // ...
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter, Write};
+use std::path::Path;
+use std::{error, fs, io};
+
+use libfmod::{Bus, Error, EventDescription, Studio, Vca};
+
+/// Identifies an FMOD Studio event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventId {
+    /// The full path of the event, e.g. `event:/Ambience/Forest`.
+    pub path: &'static str,
+    /// The GUID of the event in `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}` form.
+    pub guid: &'static str,
+}
+
+impl EventId {
+    /// Looks up the [EventDescription] by GUID, which also works without the strings bank.
+    pub fn get(&self, studio: &Studio) -> Result<EventDescription, Error> {
+        studio.get_event(self.guid)
+    }
+}
+
+/// Identifies an FMOD Studio snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnapshotId {
+    /// The full path of the snapshot, e.g. `snapshot:/Underwater`.
+    pub path: &'static str,
+    /// The GUID of the snapshot in `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}` form.
+    pub guid: &'static str,
+}
+
+impl SnapshotId {
+    /// Looks up the snapshot's [EventDescription] by GUID.
+    pub fn get(&self, studio: &Studio) -> Result<EventDescription, Error> {
+        studio.get_event(self.guid)
+    }
+}
+
+/// Identifies an FMOD Studio bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BusId {
+    /// The full path of the bus, e.g. `bus:/SFX`.
+    pub path: &'static str,
+    /// The GUID of the bus in `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}` form.
+    pub guid: &'static str,
+}
+
+impl BusId {
+    /// Looks up the [Bus] by GUID.
+    pub fn get(&self, studio: &Studio) -> Result<Bus, Error> {
+        studio.get_bus(self.guid)
+    }
+}
+
+/// Identifies an FMOD Studio VCA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VcaId {
+    /// The full path of the VCA, e.g. `vca:/Music`.
+    pub path: &'static str,
+    /// The GUID of the VCA in `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}` form.
+    pub guid: &'static str,
+}
+
+impl VcaId {
+    /// Looks up the [Vca] by GUID.
+    pub fn get(&self, studio: &Studio) -> Result<Vca, Error> {
+        studio.get_vca(self.guid)
+    }
+}
+
+/// Identifies an FMOD Studio global parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParameterId {
+    /// The full path of the parameter, e.g. `parameter:/Weather`.
+    pub path: &'static str,
+    /// The GUID of the parameter in `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}` form.
+    pub guid: &'static str,
+}
+
+impl ParameterId {
+    /// The parameter name as expected by `Studio::set_parameter_by_name`.
+    pub fn name(&self) -> &'static str {
+        self.path.rsplit('/').next().unwrap_or(self.path)
+    }
+
+    /// Sets the value of the global parameter.
+    pub fn set(&self, studio: &Studio, value: f32, ignore_seek_speed: bool) -> Result<(), Error> {
+        studio.set_parameter_by_name(self.name(), value, ignore_seek_speed)
+    }
+}
+
+/// An error while generating the identifiers.
+#[derive(Debug)]
+pub enum CodegenError {
+    /// Writing the generated file failed.
+    Io(io::Error),
+    /// Two paths map to the same Rust identifier, so one of them would be unreachable.
+    DuplicateIdentifier {
+        /// The identifier both paths map to.
+        identifier: String,
+        /// The paths of the clashing objects or folders.
+        paths: [String; 2],
+    },
+}
+
+impl Display for CodegenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::Io(error) => write!(f, "failed to write the identifiers: {error}"),
+            CodegenError::DuplicateIdentifier {
+                identifier,
+                paths: [first, second],
+            } => write!(
+                f,
+                "`{first}` and `{second}` both map to the identifier `{identifier}`, rename one of them in FMOD Studio"
+            ),
+        }
+    }
+}
+
+impl error::Error for CodegenError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CodegenError::Io(error) => Some(error),
+            CodegenError::DuplicateIdentifier { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for CodegenError {
+    fn from(error: io::Error) -> Self {
+        CodegenError::Io(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    Event,
+    Snapshot,
+    Bus,
+    Vca,
+    Parameter,
+}
+
+impl Kind {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "event" => Some(Kind::Event),
+            "snapshot" => Some(Kind::Snapshot),
+            "bus" => Some(Kind::Bus),
+            "vca" => Some(Kind::Vca),
+            "parameter" => Some(Kind::Parameter),
+            _ => None,
+        }
+    }
+
+    fn module(self) -> &'static str {
+        match self {
+            Kind::Event => "events",
+            Kind::Snapshot => "snapshots",
+            Kind::Bus => "buses",
+            Kind::Vca => "vcas",
+            Kind::Parameter => "parameters",
+        }
+    }
+
+    fn doc(self) -> &'static str {
+        match self {
+            Kind::Event => "FMOD Studio events.",
+            Kind::Snapshot => "FMOD Studio snapshots.",
+            Kind::Bus => "FMOD Studio buses.",
+            Kind::Vca => "FMOD Studio VCAs.",
+            Kind::Parameter => "FMOD Studio global parameters.",
+        }
+    }
+
+    fn id_type(self) -> &'static str {
+        match self {
+            Kind::Event => "EventId",
+            Kind::Snapshot => "SnapshotId",
+            Kind::Bus => "BusId",
+            Kind::Vca => "VcaId",
+            Kind::Parameter => "ParameterId",
+        }
+    }
+}
+
+struct Entry {
+    kind: Kind,
+    path: String,
+    guid: String,
+}
+
+#[derive(Default)]
+struct Module {
+    /// The folder path the module was created for, to detect folders that clash.
+    path: String,
+    modules: BTreeMap<String, Module>,
+    constants: BTreeMap<String, usize>,
+}
+
+/// Generator for typed FMOD identifiers. See the [module documentation](self) for usage.
+pub struct Codegen {
+    entries: Vec<Entry>,
+}
+
+impl Codegen {
+    /// Reads a `GUIDs.txt` file exported by FMOD Studio.
+    pub fn from_guids_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_guids_str(&fs::read_to_string(path)?))
+    }
+
+    /// Parses the contents of a `GUIDs.txt` file exported by FMOD Studio.
+    ///
+    /// Each line has the form `{guid} kind:/path`. Lines of kinds that have no typed identifier,
+    /// like `bank:/`, are ignored.
+    pub fn from_guids_str(contents: &str) -> Self {
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let (guid, path) = line.trim().split_once(char::is_whitespace)?;
+                let path = path.trim();
+                let (prefix, _) = path.split_once(":/")?;
+
+                if !guid.starts_with('{') || !guid.ends_with('}') {
+                    return None;
+                }
+
+                Some(Entry {
+                    kind: Kind::from_prefix(prefix)?,
+                    path: path.to_string(),
+                    guid: guid.to_string(),
+                })
+            })
+            .collect();
+
+        Codegen { entries }
+    }
+
+    /// Returns the generated Rust source, or an error if two paths map to the same identifier.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let mut roots: BTreeMap<Kind, Module> = BTreeMap::new();
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let (prefix, path) = entry.path.split_once(":/").unwrap_or_default();
+            let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+            let name = segments.pop().map_or("MASTER".to_string(), constant_name);
+
+            let mut module = roots.entry(entry.kind).or_default();
+            let mut module_path = format!("{prefix}:");
+            for segment in segments {
+                module_path = format!("{module_path}/{segment}");
+                let identifier = module_name(segment);
+                let child = module
+                    .modules
+                    .entry(identifier.clone())
+                    .or_insert_with(|| Module {
+                        path: module_path.clone(),
+                        ..Default::default()
+                    });
+
+                if child.path != module_path {
+                    return Err(CodegenError::DuplicateIdentifier {
+                        identifier,
+                        paths: [child.path.clone(), module_path],
+                    });
+                }
+                module = child;
+            }
+
+            if let Some(&other) = module.constants.get(&name) {
+                return Err(CodegenError::DuplicateIdentifier {
+                    identifier: name,
+                    paths: [self.entries[other].path.clone(), entry.path.clone()],
+                });
+            }
+            module.constants.insert(name, index);
+        }
+
+        let mut source = String::from("// Generated by bevy_fmod::codegen. Do not edit.\n");
+        for (kind, module) in &roots {
+            source.push('\n');
+            self.write_module(&mut source, kind.module(), *kind, module, 0);
+        }
+        Ok(source)
+    }
+
+    /// Writes the generated Rust source to the given file.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), CodegenError> {
+        Ok(fs::write(path, self.generate()?)?)
+    }
+
+    fn write_module(
+        &self,
+        out: &mut String,
+        name: &str,
+        kind: Kind,
+        module: &Module,
+        depth: usize,
+    ) {
+        let indent = "    ".repeat(depth);
+
+        if depth == 0 {
+            let _ = writeln!(out, "/// {}", kind.doc());
+            let _ = writeln!(out, "#[allow(dead_code)]");
+        } else {
+            let _ = writeln!(out, "{indent}#[allow(missing_docs)]");
+        }
+        let _ = writeln!(out, "{indent}pub mod {name} {{");
+
+        for (constant, index) in &module.constants {
+            let entry = &self.entries[*index];
+            let _ = writeln!(out, "{indent}    /// `{}`", entry.path);
+            let _ = writeln!(
+                out,
+                "{indent}    pub const {constant}: ::bevy_fmod::codegen::{ty} = ::bevy_fmod::codegen::{ty} {{ path: {path:?}, guid: {guid:?} }};",
+                ty = kind.id_type(),
+                path = entry.path,
+                guid = entry.guid,
+            );
+        }
+
+        for (child_name, child) in &module.modules {
+            self.write_module(out, child_name, kind, child, depth + 1);
+        }
+
+        let _ = writeln!(out, "{indent}}}");
+    }
+}
+
+fn words(segment: &str) -> Vec<&str> {
+    segment
+        .split(|c: char| c == '_' || !unicode_ident::is_xid_continue(c))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Prefixes names that can't start an identifier, like `2D`, with `_`. Names without any letters
+/// or digits get a hash of the segment, so they stay stable when other objects are added.
+fn escape_start(name: String, segment: &str) -> String {
+    if name.is_empty() {
+        // FNV-1a, as the std hashers don't guarantee stable output across Rust versions
+        let hash = segment.bytes().fold(0x811c9dc5_u32, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(0x01000193)
+        });
+        format!("_{hash:08x}")
+    } else if name.starts_with(|c: char| !unicode_ident::is_xid_start(c)) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+fn module_name(segment: &str) -> String {
+    let name = escape_start(words(segment).join("_").to_lowercase(), segment);
+
+    match name.as_str() {
+        "as" | "break" | "const" | "continue" | "else" | "enum" | "extern" | "false" | "fn"
+        | "for" | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move" | "mut"
+        | "pub" | "ref" | "return" | "static" | "struct" | "trait" | "true" | "type" | "unsafe"
+        | "use" | "where" | "while" | "async" | "await" | "dyn" | "abstract" | "become" | "box"
+        | "do" | "final" | "macro" | "override" | "priv" | "typeof" | "unsized" | "virtual"
+        | "yield" | "try" | "gen" => format!("r#{name}"),
+        // These can't be raw identifiers
+        "crate" | "self" | "super" => format!("{name}_"),
+        _ => name,
+    }
+}
+
+fn constant_name(segment: &str) -> String {
+    escape_start(words(segment).join("_").to_uppercase(), segment)
+}
@@ -0,0 +1,206 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use bevy::log::tracing::field::Empty;
+use bevy::log::{debug, error, info_span};
+use bevy::prelude::{Res, ResMut, Resource};
+use libfmod::ffi::{
+    FMOD_INIT_3D_RIGHTHANDED, FMOD_STUDIO_COMMANDCAPTURE_FILEFLUSH,
+    FMOD_STUDIO_COMMANDCAPTURE_NORMAL, FMOD_STUDIO_COMMANDREPLAY_FAST_FORWARD,
+    FMOD_STUDIO_COMMANDREPLAY_SKIP_CLEANUP, FMOD_STUDIO_INIT_NORMAL,
+};
+use libfmod::{CommandReplay, Error, OutputType, PlaybackState, Studio};
+
+use crate::fmod_error::FmodErrorReporter;
+use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+
+/// A resource that records all FMOD Studio API calls to a file, so QA can attach an audio repro
+/// to a bug report. Play a capture back with the [CommandReplayRunner], or open it in the
+/// profiler of FMOD Studio.
+///
+/// Like [FmodBuses](crate::FmodBuses), starting and stopping is applied once per frame in
+/// [`PostUpdate`](bevy::app::PostUpdate), right before FMOD Studio is updated.
+///
+/// ```
+/// # use bevy::prelude::ResMut;
+/// # use bevy_fmod::prelude::FmodCommandCapture;
+/// fn toggle_capture(mut capture: ResMut<FmodCommandCapture>) {
+///     if capture.is_capturing() {
+///         capture.stop();
+///     } else {
+///         capture.start("audio_capture.cmd.cap", true);
+///     }
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct FmodCommandCapture {
+    path: Option<PathBuf>,
+    command: Option<CaptureCommand>,
+}
+
+enum CaptureCommand {
+    Start { path: PathBuf, flush: bool },
+    Stop,
+}
+
+impl FmodCommandCapture {
+    /// Queues starting a capture to the file at the given path, which stops any running capture.
+    ///
+    /// With `flush`, the file is flushed after every command, so the capture survives a crash at
+    /// the cost of performance.
+    pub fn start(&mut self, path: impl Into<PathBuf>, flush: bool) {
+        self.command = Some(CaptureCommand::Start {
+            path: path.into(),
+            flush,
+        });
+    }
+
+    /// Queues stopping the running capture.
+    pub fn stop(&mut self) {
+        self.command = Some(CaptureCommand::Stop);
+    }
+
+    /// Whether a capture is running. A queued start only counts once it succeeded.
+    pub fn is_capturing(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// The file of the running capture.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub(crate) fn apply(
+        mut capture: ResMut<FmodCommandCapture>,
+        studio: Res<FmodStudio>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let Some(command) = capture.command.take() else {
+            return;
+        };
+
+        let ffi = FfiScope::new(
+            info_span!("fmod::apply_command_capture", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        if capture.path.take().is_some()
+            && let Err(e) = ffi.call(|| studio.stop_command_capture())
+        {
+            errors.report(e);
+        }
+
+        if let CaptureCommand::Start { path, flush } = command {
+            let flags = if flush {
+                FMOD_STUDIO_COMMANDCAPTURE_FILEFLUSH
+            } else {
+                FMOD_STUDIO_COMMANDCAPTURE_NORMAL
+            };
+
+            match ffi.call(|| studio.start_command_capture(&path.to_string_lossy(), flags)) {
+                Ok(()) => {
+                    debug!("Capturing FMOD commands to {:?}", path);
+                    capture.path = Some(path);
+                }
+                Err(e) => errors.report(e),
+            }
+        }
+    }
+}
+
+/// Plays back a capture of the [FmodCommandCapture] in a separate FMOD Studio system without
+/// sound output, e.g. in a test that reproduces a bug report.
+///
+/// The banks are loaded from the paths they were loaded from when the capture was recorded.
+///
+/// ```no_run
+/// # use bevy_fmod::prelude::CommandReplayRunner;
+/// # use std::time::Duration;
+/// let runner = CommandReplayRunner::new("audio_capture.cmd.cap").unwrap();
+/// assert!(runner.run(Duration::from_secs(10)).unwrap());
+/// ```
+pub struct CommandReplayRunner {
+    studio: Studio,
+    replay: CommandReplay,
+}
+
+impl CommandReplayRunner {
+    /// Creates a FMOD Studio system with `NoSound` output and loads the capture at the given path.
+    ///
+    /// The objects created by the replay are kept after it finished, so their state can be
+    /// inspected through [`CommandReplayRunner::studio`].
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let studio = Studio::create()?;
+
+        let result = studio
+            .get_core_system()
+            .and_then(|system| system.set_output(OutputType::NoSound))
+            .and_then(|()| {
+                studio.initialize(
+                    1024,
+                    FMOD_STUDIO_INIT_NORMAL,
+                    FMOD_INIT_3D_RIGHTHANDED,
+                    None,
+                )
+            })
+            .and_then(|()| {
+                studio.load_command_replay(
+                    &path.as_ref().to_string_lossy(),
+                    FMOD_STUDIO_COMMANDREPLAY_FAST_FORWARD | FMOD_STUDIO_COMMANDREPLAY_SKIP_CLEANUP,
+                )
+            });
+
+        match result {
+            Ok(replay) => Ok(CommandReplayRunner { studio, replay }),
+            Err(e) => {
+                let _ = studio.release();
+                Err(e)
+            }
+        }
+    }
+
+    /// The loaded replay, e.g. to inspect its commands.
+    pub fn replay(&self) -> CommandReplay {
+        self.replay
+    }
+
+    /// The FMOD Studio system the replay runs in, e.g. to check the state of events after
+    /// running it. It is released when the runner is dropped.
+    pub fn studio(&self) -> &Studio {
+        &self.studio
+    }
+
+    /// Plays back all commands of the capture, ignoring their timing.
+    ///
+    /// Returns whether the replay finished within `timeout`. Otherwise it is stopped, e.g. when
+    /// the capture contains a command that never completes.
+    pub fn run(&self, timeout: Duration) -> Result<bool, Error> {
+        let deadline = Instant::now() + timeout;
+        self.replay.start()?;
+
+        loop {
+            self.studio.update()?;
+
+            if matches!(self.replay.get_playback_state()?, PlaybackState::Stopped) {
+                return Ok(true);
+            }
+
+            if Instant::now() >= deadline {
+                self.replay.stop()?;
+                self.studio.update()?;
+                return Ok(false);
+            }
+        }
+    }
+}
+
+impl Drop for CommandReplayRunner {
+    fn drop(&mut self) {
+        // Releasing the system releases the replay as well
+        if let Err(e) = self.studio.release() {
+            error!("Failed to release FMOD command replay system: {}", e);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::math::Vec3;
+use bevy::math::primitives::Triangle3d;
+#[cfg(feature = "geometry")]
+use bevy::mesh::{Mesh, MeshTrianglesError};
+use bevy::prelude::{
+    Commands, Component, DetectChanges, DetectChangesMut, Entity, GlobalTransform, Query, Ref, Res,
+    With, Without,
+};
+
+use crate::attributes_3d::attributes3d;
+use crate::components::audio_listener::AudioListener;
+use crate::components::audio_source::{AudioSource, InvalidAudioSource};
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::spatial_settings::SpatialSettings;
+
+/// Component that spreads the [AudioSource] on the same entity over a shape, e.g. a river, a road
+/// or a coastline.
+///
+/// Every frame, the source is placed at the point of the shape that is nearest to the closest
+/// [AudioListener]. When the listener is inside a volume, the source is placed at the listener,
+/// so the sound surrounds it. The point is stored in the [EmitterPosition] of the entity.
+///
+/// Shapes are defined in the entity's local space and follow its [GlobalTransform]. With
+/// non-uniform scale, the nearest point is only approximate.
+///
+/// ```
+/// # use bevy::math::Vec3;
+/// # use bevy_fmod::components::{AreaShape, AudioArea};
+/// let river = AudioArea::new(AreaShape::Polyline(vec![
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(20.0, 0.0, 5.0),
+///     Vec3::new(40.0, 0.0, 0.0),
+/// ]));
+///
+/// let lake = AudioArea::new(AreaShape::Sphere { radius: 15.0 }).with_inside_parameter("Inside");
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct AudioArea {
+    /// The shape the sound is spread over.
+    pub shape: AreaShape,
+    /// Name of an event parameter that is set to `1.0` while the listener is inside the volume
+    /// and to `0.0` otherwise.
+    pub inside_parameter: Option<&'static str>,
+    inside: Option<bool>,
+}
+
+/// The position an [AudioSource] is heard at, when it differs from the entity's
+/// [GlobalTransform], e.g. the point of an [AudioArea] nearest to the listener.
+///
+/// Distance culling, occlusion and the debug gizmos use it instead of the transform.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct EmitterPosition(pub Vec3);
+
+/// The shape of an [AudioArea], in the entity's local space.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AreaShape {
+    /// Connected line segments, e.g. along a river. Polylines have no inside.
+    Polyline(Vec<Vec3>),
+    /// An axis-aligned box centered on the entity.
+    Box {
+        /// Half the size of the box along each axis.
+        half_size: Vec3,
+    },
+    /// A sphere centered on the entity.
+    Sphere {
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// A closed triangle mesh whose triangles face outwards.
+    Triangles(Vec<Triangle3d>),
+}
+
+impl AudioArea {
+    /// Creates an area of the given shape.
+    #[must_use]
+    pub fn new(shape: AreaShape) -> Self {
+        AudioArea {
+            shape,
+            inside_parameter: None,
+            inside: None,
+        }
+    }
+
+    /// Sets the event parameter that tells whether the listener is inside the volume.
+    #[must_use]
+    pub fn with_inside_parameter(mut self, parameter: &'static str) -> Self {
+        self.inside_parameter = Some(parameter);
+        self
+    }
+
+    /// Whether the listener was inside the volume in the last frame.
+    pub fn is_inside(&self) -> bool {
+        self.inside.unwrap_or(false)
+    }
+
+    /// Places the sources at the nearest point of their area. The 3D attributes are only sent
+    /// when that point, the orientation or the [SpatialSettings] changed.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn update_3d_attributes(
+        mut commands: Commands,
+        mut areas: Query<
+            (
+                Entity,
+                &mut AudioArea,
+                Ref<AudioSource>,
+                Ref<GlobalTransform>,
+                Option<&mut EmitterPosition>,
+            ),
+            Without<InvalidAudioSource>,
+        >,
+        listeners: Query<&GlobalTransform, With<AudioListener>>,
+        spatial_settings: Res<SpatialSettings>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let ffi = FfiScope::new(
+            info_span!("fmod::set_area_attributes", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        for (entity, mut area, audio_source, transform, emitter) in &mut areas {
+            let to_local = transform.affine().inverse();
+
+            // The nearest point to the closest listener
+            let Some((position, inside, _)) = listeners
+                .iter()
+                .map(|listener| {
+                    let listener = listener.translation();
+                    let (point, inside) = area
+                        .shape
+                        .nearest_point(to_local.transform_point3(listener));
+
+                    if inside {
+                        (listener, true, 0.0)
+                    } else {
+                        let point = transform.transform_point(point);
+                        (point, false, point.distance_squared(listener))
+                    }
+                })
+                .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+            else {
+                continue;
+            };
+
+            let moved = match emitter {
+                Some(mut emitter) => emitter.set_if_neq(EmitterPosition(position)),
+                None => {
+                    commands.entity(entity).insert(EmitterPosition(position));
+                    true
+                }
+            };
+
+            // The position jumps with the listener, which must not cause a Doppler effect
+            if (moved
+                || transform.is_changed()
+                || audio_source.is_changed()
+                || spatial_settings.is_changed())
+                && let Err(e) = ffi.call(|| {
+                    audio_source.set_3d_attributes(attributes3d(
+                        &spatial_settings,
+                        position,
+                        Vec3::ZERO,
+                        *transform.forward(),
+                        *transform.up(),
+                    ))
+                })
+            {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+
+            if area.inside == Some(inside) {
+                continue;
+            }
+            area.inside = Some(inside);
+
+            if let Some(parameter) = area.inside_parameter
+                && let Err(e) = ffi.call(|| {
+                    audio_source.set_parameter_by_name(
+                        parameter,
+                        if inside { 1.0 } else { 0.0 },
+                        false,
+                    )
+                })
+            {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+        }
+    }
+}
+
+impl AreaShape {
+    /// Collects the triangles of a mesh with a triangle list topology.
+    #[cfg(feature = "geometry")]
+    pub fn from_mesh(mesh: &Mesh) -> Result<Self, MeshTrianglesError> {
+        Ok(AreaShape::Triangles(mesh.triangles()?.collect()))
+    }
+
+    /// Returns the point of the shape that is nearest to `point`, and whether `point` is inside
+    /// the volume. All positions are in the shape's local space.
+    pub fn nearest_point(&self, point: Vec3) -> (Vec3, bool) {
+        match self {
+            AreaShape::Polyline(points) => {
+                let nearest = match points.as_slice() {
+                    [] => Vec3::ZERO,
+                    [single] => *single,
+                    points => points
+                        .windows(2)
+                        .map(|segment| nearest_point_on_segment(point, segment[0], segment[1]))
+                        .min_by(|a, b| {
+                            a.distance_squared(point)
+                                .total_cmp(&b.distance_squared(point))
+                        })
+                        .unwrap_or(Vec3::ZERO),
+                };
+                (nearest, false)
+            }
+            AreaShape::Box { half_size } => {
+                let nearest = point.clamp(-*half_size, *half_size);
+                (nearest, nearest == point)
+            }
+            AreaShape::Sphere { radius } => {
+                if point.length() <= *radius {
+                    (point, true)
+                } else {
+                    (point.normalize() * *radius, false)
+                }
+            }
+            AreaShape::Triangles(triangles) => triangles
+                .iter()
+                .map(|triangle| {
+                    let nearest = nearest_point_on_triangle(point, triangle);
+                    // Behind the nearest triangle means inside a closed, outward-facing mesh
+                    let inside = triangle
+                        .normal()
+                        .is_ok_and(|normal| (point - nearest).dot(*normal) < 0.0);
+                    (nearest, inside)
+                })
+                .min_by(|(a, _), (b, _)| {
+                    a.distance_squared(point)
+                        .total_cmp(&b.distance_squared(point))
+                })
+                .map_or((Vec3::ZERO, false), |(nearest, inside)| {
+                    if inside {
+                        (point, true)
+                    } else {
+                        (nearest, false)
+                    }
+                }),
+        }
+    }
+}
+
+fn nearest_point_on_segment(point: Vec3, start: Vec3, end: Vec3) -> Vec3 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+
+    if length_squared == 0.0 {
+        return start;
+    }
+
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    start + segment * t
+}
+
+/// From Ericson, Real-Time Collision Detection, 5.1.5.
+fn nearest_point_on_triangle(point: Vec3, triangle: &Triangle3d) -> Vec3 {
+    let [a, b, c] = triangle.vertices;
+    let ab = b - a;
+    let ac = c - a;
+    let ap = point - a;
+
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = point - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = point - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denominator = 1.0 / (va + vb + vc);
+    a + ab * (vb * denominator) + ac * (vc * denominator)
+}
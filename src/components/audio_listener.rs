@@ -1,40 +1,139 @@
+use bevy::log::tracing::field::Empty;
+use bevy::log::{info_span, warn};
 use bevy::math::Vec3;
-use bevy::prelude::{Component, GlobalTransform, Query, Res, With};
+use bevy::prelude::{Component, Entity, GlobalTransform, Local, Query, Res, With};
 
-use crate::attributes_3d::attributes3d;
+use crate::attributes_3d::{attributes3d, to_fmod_vec};
 use crate::components::velocity::Velocity;
+use crate::fmod_error::{FmodError, FmodErrorReporter};
 use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::spatial_settings::SpatialSettings;
+
+/// The maximum number of listeners FMOD supports (`FMOD_MAX_LISTENERS`).
+const MAX_LISTENERS: usize = 8;
 
 /// Component that represents an audio listener in 3D space.
 ///
-/// See the [`Velocity`] component for information on enabling the Doppler effect.
+/// Multiple listeners are supported, e.g. for split-screen games. Every listener gets its own
+/// FMOD listener index, ordered by [Entity], and the number of FMOD listeners follows the number
+/// of [AudioListener]s as they spawn and despawn. FMOD supports up to 8 listeners.
+///
+/// See the [`Velocity`] component for information on enabling the Doppler effect,
+/// [`ListenerWeight`] for crossfading between listeners and [`ListenerAttenuationTarget`] for
+/// attenuating by distance to another entity.
 #[derive(Component, Default)]
 pub struct AudioListener;
 
+/// Optional weight of an [AudioListener] between `0.0` and `1.0`, used to crossfade between
+/// listeners, e.g. when switching cameras. Listeners without this component have a weight of
+/// `1.0`.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ListenerWeight(pub f32);
+
+impl Default for ListenerWeight {
+    fn default() -> Self {
+        ListenerWeight(1.0)
+    }
+}
+
+/// Optional target of an [AudioListener] whose position is used for distance attenuation instead
+/// of the listener's own position. Panning still uses the listener's position and orientation.
+///
+/// This is useful for third-person games, where the listener sits on the camera but distance
+/// attenuation should be relative to the player character.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ListenerAttenuationTarget(pub Entity);
+
 impl AudioListener {
+    #[allow(clippy::type_complexity)]
     pub(crate) fn update_3d_attributes(
-        query: Query<(&GlobalTransform, Option<&Velocity>), With<AudioListener>>,
+        query: Query<
+            (
+                Entity,
+                &GlobalTransform,
+                Option<&Velocity>,
+                Option<&ListenerWeight>,
+                Option<&ListenerAttenuationTarget>,
+            ),
+            With<AudioListener>,
+        >,
+        transforms: Query<&GlobalTransform>,
         studio: Res<FmodStudio>,
+        spatial_settings: Res<SpatialSettings>,
+        // The number of listener entities in the last frame, including ignored ones
+        mut num_listeners: Local<Option<usize>>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
     ) {
-        if let Ok((transform, vel_component)) = query.single() {
+        let mut listeners: Vec<_> = query.iter().collect();
+        listeners.sort_unstable_by_key(|(entity, ..)| *entity);
+
+        // Only warn when the limit is first exceeded, not every frame
+        let spawned = listeners.len();
+        let was_exceeded = num_listeners.is_some_and(|n| n > MAX_LISTENERS);
+        if spawned > MAX_LISTENERS && !was_exceeded {
+            warn!(
+                "FMOD supports up to {} listeners, ignoring {} listeners",
+                MAX_LISTENERS,
+                spawned - MAX_LISTENERS
+            );
+        }
+        listeners.truncate(MAX_LISTENERS);
+
+        let ffi = FfiScope::new(
+            info_span!(
+                "fmod::set_listener_attributes",
+                listeners = listeners.len(),
+                ffi_calls = Empty
+            ),
+            &ffi_calls,
+        );
+
+        // FMOD always has at least one listener
+        let count = listeners.len().max(1);
+        if num_listeners.is_none_or(|n| n.clamp(1, MAX_LISTENERS) != count)
+            && let Err(e) = ffi.call(|| studio.set_num_listeners(count as i32))
+        {
+            errors.report(e);
+            return;
+        }
+        *num_listeners = Some(spawned);
+
+        for (index, (entity, transform, vel_component, weight, attenuation_target)) in
+            listeners.into_iter().enumerate()
+        {
             let mut velocity = Vec3::ZERO;
 
             if let Some(vel_component) = vel_component {
                 velocity = vel_component.current_velocity;
             }
 
-            studio
-                .set_listener_attributes(
-                    0,
-                    attributes3d(
-                        transform.translation(),
-                        velocity,
-                        *transform.forward(),
-                        *transform.up(),
-                    ),
-                    None,
-                )
-                .unwrap();
+            let attenuation_position = attenuation_target
+                .and_then(|target| transforms.get(target.0).ok())
+                .map(|target_transform| {
+                    to_fmod_vec(spatial_settings.map_vector(target_transform.translation()))
+                });
+
+            let attributes = attributes3d(
+                &spatial_settings,
+                transform.translation(),
+                velocity,
+                *transform.forward(),
+                *transform.up(),
+            );
+
+            if let Err(e) = ffi
+                .call(|| {
+                    studio.set_listener_attributes(index as i32, attributes, attenuation_position)
+                })
+                .and_then(|()| {
+                    let weight = weight.copied().unwrap_or_default().0;
+                    ffi.call(|| studio.set_listener_weight(index as i32, weight))
+                })
+            {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
         }
     }
 }
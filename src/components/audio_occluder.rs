@@ -0,0 +1,244 @@
+use bevy::asset::{AssetEvent, Assets, Handle};
+use bevy::log::tracing::field::Empty;
+use bevy::log::{error, info_span};
+use bevy::math::primitives::Triangle3d;
+use bevy::math::{Mat3, Vec3};
+use bevy::mesh::Mesh;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::{
+    Changed, Component, DetectChanges, DetectChangesMut, Entity, GlobalTransform, MessageReader,
+    Query, Res,
+};
+use libfmod::{Error, Geometry, Studio};
+
+use crate::attributes_3d::to_fmod_vec;
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::spatial_settings::SpatialSettings;
+
+/// Component that blocks sound with the triangles of a [Mesh], using the FMOD Geometry API.
+///
+/// The geometry is built once the mesh is loaded and rebuilt whenever the component or the mesh
+/// asset changes, which also retries building geometry that failed before. Its
+/// position, rotation and scale follow the entity's [GlobalTransform], and it is released when
+/// the component is removed or its entity despawns.
+///
+/// Only [`SpatialMode::ThreeD`](crate::SpatialMode::ThreeD) is supported, and the mesh must use
+/// a triangle list topology.
+///
+/// ```ignore
+/// commands.spawn((
+///     AudioOccluder::new(wall_mesh.clone()).with_occlusion(0.8, 0.5),
+///     Transform::from_xyz(0.0, 1.0, -4.0),
+/// ));
+/// ```
+#[derive(Component)]
+pub struct AudioOccluder {
+    /// The mesh whose triangles form the geometry.
+    pub mesh: Handle<Mesh>,
+    /// How much sound passing through the geometry is attenuated, from `0.0` to `1.0`.
+    pub direct_occlusion: f32,
+    /// How much reverb passing through the geometry is attenuated, from `0.0` to `1.0`.
+    pub reverb_occlusion: f32,
+    /// Whether the triangles block sound from both sides. Otherwise only their front faces do.
+    pub double_sided: bool,
+    geometry: GeometryState,
+}
+
+enum GeometryState {
+    Pending,
+    Built(Geometry),
+    Failed,
+}
+
+impl AudioOccluder {
+    /// Creates a fully occluding, double-sided occluder from the given mesh.
+    #[must_use]
+    pub fn new(mesh: Handle<Mesh>) -> Self {
+        AudioOccluder {
+            mesh,
+            direct_occlusion: 1.0,
+            reverb_occlusion: 1.0,
+            double_sided: true,
+            geometry: GeometryState::Pending,
+        }
+    }
+
+    /// Sets the direct and reverb occlusion factors.
+    #[must_use]
+    pub fn with_occlusion(mut self, direct_occlusion: f32, reverb_occlusion: f32) -> Self {
+        self.direct_occlusion = direct_occlusion;
+        self.reverb_occlusion = reverb_occlusion;
+        self
+    }
+
+    /// Sets whether the triangles block sound from both sides.
+    #[must_use]
+    pub fn with_double_sided(mut self, double_sided: bool) -> Self {
+        self.double_sided = double_sided;
+        self
+    }
+
+    /// The FMOD [Geometry], once it has been built.
+    pub fn geometry(&self) -> Option<Geometry> {
+        match self.geometry {
+            GeometryState::Built(geometry) => Some(geometry),
+            GeometryState::Pending | GeometryState::Failed => None,
+        }
+    }
+
+    pub(crate) fn release(&self) -> Result<(), Error> {
+        match self.geometry() {
+            Some(geometry) => geometry.release(),
+            None => Ok(()),
+        }
+    }
+
+    fn build(
+        &self,
+        ffi: &FfiScope,
+        studio: &Studio,
+        triangles: Vec<Triangle3d>,
+        settings: &SpatialSettings,
+    ) -> Result<Geometry, Error> {
+        let geometry = ffi.call(|| studio.get_core_system()).and_then(|system| {
+            ffi.call(|| system.create_geometry(triangles.len() as i32, 3 * triangles.len() as i32))
+        })?;
+
+        for triangle in triangles {
+            let vertices = triangle
+                .vertices
+                .map(|vertex| to_fmod_vec(settings.axes.map_vector(vertex)));
+
+            if let Err(e) = ffi.call(|| {
+                geometry.add_polygon(
+                    self.direct_occlusion,
+                    self.reverb_occlusion,
+                    self.double_sided,
+                    3,
+                    vertices.as_ptr(),
+                )
+            }) {
+                // Don't leak the partially built geometry
+                let _ = ffi.call(|| geometry.release());
+                return Err(e);
+            }
+        }
+
+        Ok(geometry)
+    }
+
+    fn sync_transform(
+        ffi: &FfiScope,
+        geometry: Geometry,
+        transform: &GlobalTransform,
+        settings: &SpatialSettings,
+    ) -> Result<(), Error> {
+        let (scale, _, translation) = transform.to_scale_rotation_translation();
+        let (forward, up) = settings.map_orientation(*transform.forward(), *transform.up());
+
+        // Vertices are mapped into FMOD space when the geometry is built, so the scale has to be
+        // mapped the same way: M * S * M⁻¹
+        let axes = settings.axes.0;
+        let scale = axes * Mat3::from_diagonal(scale) * axes.inverse();
+
+        let scale = Vec3::new(scale.x_axis.x, scale.y_axis.y, scale.z_axis.z);
+
+        ffi.call(|| geometry.set_position(to_fmod_vec(settings.map_vector(translation))))?;
+        ffi.call(|| geometry.set_rotation(Some(to_fmod_vec(forward)), Some(to_fmod_vec(up))))?;
+        ffi.call(|| geometry.set_scale(to_fmod_vec(scale)))
+    }
+
+    pub(crate) fn update_geometry(
+        mut occluders: Query<(Entity, &mut AudioOccluder, &GlobalTransform)>,
+        meshes: Option<Res<Assets<Mesh>>>,
+        mesh_events: Option<MessageReader<AssetEvent<Mesh>>>,
+        studio: Res<FmodStudio>,
+        settings: Res<SpatialSettings>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let (Some(meshes), Some(mut mesh_events)) = (meshes, mesh_events) else {
+            return;
+        };
+
+        let ffi = FfiScope::new(
+            info_span!("fmod::update_geometry", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        let modified_meshes = mesh_events
+            .read()
+            .filter_map(|event| match event {
+                AssetEvent::Modified { id } => Some(*id),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        for (entity, mut occluder, transform) in &mut occluders {
+            let rebuild = occluder.is_changed()
+                || settings.is_changed()
+                || modified_meshes.contains(&occluder.mesh.id());
+            if !rebuild && !matches!(occluder.geometry, GeometryState::Pending) {
+                continue;
+            }
+
+            let Some(mesh) = meshes.get(&occluder.mesh) else {
+                continue;
+            };
+
+            if let Some(geometry) = occluder.geometry()
+                && let Err(e) = ffi.call(|| geometry.release())
+            {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+
+            let occluder = occluder.bypass_change_detection();
+            let triangles = match mesh.triangles() {
+                Ok(triangles) => triangles.collect(),
+                Err(e) => {
+                    error!(
+                        "Failed to build audio occluder geometry of {}: {}",
+                        entity, e
+                    );
+                    occluder.geometry = GeometryState::Failed;
+                    continue;
+                }
+            };
+
+            occluder.geometry = match occluder.build(&ffi, &studio, triangles, &settings) {
+                Ok(geometry) => {
+                    if let Err(e) = Self::sync_transform(&ffi, geometry, transform, &settings) {
+                        errors.report(FmodError::new(e).with_entity(entity));
+                    }
+                    GeometryState::Built(geometry)
+                }
+                Err(e) => {
+                    errors.report(FmodError::new(e).with_entity(entity));
+                    GeometryState::Failed
+                }
+            };
+        }
+    }
+
+    pub(crate) fn update_transforms(
+        occluders: Query<(Entity, &AudioOccluder, &GlobalTransform), Changed<GlobalTransform>>,
+        settings: Res<SpatialSettings>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let ffi = FfiScope::new(
+            info_span!("fmod::update_geometry_transforms", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        for (entity, occluder, transform) in &occluders {
+            if let Some(geometry) = occluder.geometry()
+                && let Err(e) = Self::sync_transform(&ffi, geometry, transform, &settings)
+            {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+        }
+    }
+}
@@ -1,8 +1,25 @@
 use crate::attributes_3d::attributes3d;
+use crate::components::audio_area::AudioArea;
 use crate::components::velocity::Velocity;
+use crate::fmod_error::{FmodError, FmodErrorMessage, FmodErrorReporter};
+use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::round_robin::round_robin;
+use crate::spatial_settings::SpatialSettings;
+use bevy::log::tracing::field::Empty;
+use bevy::log::{debug, info_span, warn};
 use bevy::math::Vec3;
-use bevy::prelude::{Component, Deref, DerefMut, GlobalTransform, Query};
-use libfmod::{EventInstance, StopMode};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::{
+    Added, Changed, Commands, Component, Deref, DerefMut, DetectChanges, Entity, GlobalTransform,
+    Local, MessageReader, Or, Query, Res, With, Without,
+};
+use libfmod::ffi::FMOD_ERR_INVALID_HANDLE;
+use libfmod::{Error, EventInstance, Guid, StopMode};
+
+/// How many sources [`AudioSource::validate`] checks per frame, besides those that FMOD reported
+/// an invalid handle for.
+const VALIDATIONS_PER_FRAME: usize = 8;
 
 /// See the [`Velocity`] component for information on enabling the Doppler effect.
 #[derive(Component, Deref, DerefMut)]
@@ -16,72 +33,254 @@ pub struct AudioSource {
     pub despawn_stop_mode: StopMode,
 }
 
+/// Marker for [AudioSource]s whose [EventInstance] is no longer valid, e.g. because its bank was
+/// unloaded or the instance was released elsewhere. A few sources are checked every frame in
+/// turn, and a source is checked right away when FMOD reports an invalid handle for it.
+///
+/// Invalid sources are skipped by the systems of this crate and their instance is not stopped or
+/// released when they despawn. Add [RecreateAudioSource] to recreate the instance once its event
+/// is available again.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct InvalidAudioSource;
+
+/// Recreates the [EventInstance] of an [AudioSource] when it became invalid and its event is
+/// available again, e.g. after the bank was reloaded.
+///
+/// The event is remembered by its GUID while the instance is still valid.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RecreateAudioSource {
+    /// Whether to start the recreated instance.
+    pub start: bool,
+    event_id: Option<Guid>,
+}
+
+impl RecreateAudioSource {
+    /// Recreates the instance and starts it if `start` is true.
+    #[must_use]
+    pub fn new(start: bool) -> Self {
+        RecreateAudioSource {
+            start,
+            event_id: None,
+        }
+    }
+}
+
 impl AudioSource {
+    /// Remembers the event of sources that are recreated when they become invalid.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn remember_events(
+        mut sources: Query<
+            (&AudioSource, &mut RecreateAudioSource),
+            (
+                Or<(Changed<AudioSource>, Added<RecreateAudioSource>)>,
+                Without<InvalidAudioSource>,
+            ),
+        >,
+        ffi_calls: Res<FmodFfiCalls>,
+    ) {
+        let ffi = FfiScope::new(
+            info_span!("fmod::remember_events", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        for (audio_source, mut recreate) in &mut sources {
+            if let Ok(event_id) = ffi
+                .call(|| audio_source.get_description())
+                .and_then(|description| ffi.call(|| description.get_id()))
+            {
+                recreate.event_id = Some(event_id);
+            }
+        }
+    }
+
+    /// Marks sources whose instance is no longer valid with [InvalidAudioSource].
+    ///
+    /// Sources that FMOD reported an invalid handle for are checked right away. The others are
+    /// checked a few per frame in turn, so static sources are noticed eventually without
+    /// checking every source every frame.
+    pub(crate) fn validate(
+        mut commands: Commands,
+        sources: Query<(Entity, &AudioSource), Without<InvalidAudioSource>>,
+        mut error_messages: MessageReader<FmodErrorMessage>,
+        mut next_source: Local<usize>,
+        ffi_calls: Res<FmodFfiCalls>,
+    ) {
+        let ffi = FfiScope::new(
+            info_span!("fmod::validate_sources", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        let mut check = |(entity, audio_source): (Entity, &AudioSource)| {
+            if !ffi.call(|| audio_source.is_valid()) {
+                warn!(
+                    "Event instance of audio source {} is no longer valid",
+                    entity
+                );
+                commands.entity(entity).insert(InvalidAudioSource);
+            }
+        };
+
+        let reported = error_messages
+            .read()
+            .filter(|message| message.code == Some(FMOD_ERR_INVALID_HANDLE))
+            .filter_map(|message| message.entity)
+            .collect::<HashSet<_>>();
+        reported
+            .into_iter()
+            .filter_map(|entity| sources.get(entity).ok())
+            .for_each(&mut check);
+
+        round_robin(sources.iter(), &mut next_source, VALIDATIONS_PER_FRAME).for_each(&mut check);
+    }
+
+    /// Recreates the instances of invalid sources whose event has become available again.
+    pub(crate) fn recreate(
+        mut commands: Commands,
+        mut sources: Query<
+            (Entity, &mut AudioSource, &RecreateAudioSource),
+            With<InvalidAudioSource>,
+        >,
+        studio: Res<FmodStudio>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let ffi = FfiScope::new(
+            info_span!("fmod::recreate_sources", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        for (entity, mut audio_source, recreate) in &mut sources {
+            let Some(event_id) = recreate.event_id else {
+                continue;
+            };
+
+            // Fails until the bank of the event is loaded again
+            let Ok(event_instance) = ffi
+                .call(|| studio.get_event_by_id(event_id))
+                .and_then(|description| ffi.call(|| description.create_instance()))
+            else {
+                continue;
+            };
+
+            if recreate.start
+                && let Err(e) = ffi.call(|| event_instance.start())
+            {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+
+            debug!("Recreated event instance of audio source {}", entity);
+            audio_source.event_instance = event_instance;
+            commands.entity(entity).remove::<InvalidAudioSource>();
+        }
+    }
+
+    /// Sends the 3D attributes of sources that were added or moved, or whose [Velocity] changed.
+    /// All sources are updated when the [SpatialSettings] change.
+    ///
+    /// Unchanged sources are skipped, so static sources cost no FFI calls at all. Sources that
+    /// fail don't keep the others from being updated.
+    #[allow(clippy::type_complexity)]
     pub(crate) fn update_3d_attributes(
-        mut query: Query<(&AudioSource, &GlobalTransform, Option<&Velocity>)>,
+        all_sources: Query<
+            (Entity, &AudioSource, &GlobalTransform, Option<&Velocity>),
+            (Without<AudioArea>, Without<InvalidAudioSource>),
+        >,
+        changed_sources: Query<
+            (Entity, &AudioSource, &GlobalTransform, Option<&Velocity>),
+            (
+                Or<(
+                    Changed<AudioSource>,
+                    Changed<GlobalTransform>,
+                    Changed<Velocity>,
+                )>,
+                Without<AudioArea>,
+                Without<InvalidAudioSource>,
+            ),
+        >,
+        spatial_settings: Res<SpatialSettings>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
     ) {
-        query
-            .iter_mut()
-            .for_each(|(audio_source, transform, vel_component)| {
-                let mut velocity = Vec3::ZERO;
-
-                if let Some(vel_component) = vel_component {
-                    velocity = vel_component.current_velocity;
-                }
-
-                audio_source
-                    .set_3d_attributes(attributes3d(
-                        transform.translation(),
-                        velocity,
-                        *transform.forward(),
-                        *transform.up(),
-                    ))
-                    .unwrap();
-            });
+        let ffi = FfiScope::new(
+            info_span!(
+                "fmod::set_3d_attributes",
+                sources = Empty,
+                ffi_calls = Empty
+            ),
+            &ffi_calls,
+        );
+
+        let mut sent = 0;
+        let mut send = |(entity, audio_source, transform, velocity): (
+            Entity,
+            &AudioSource,
+            &GlobalTransform,
+            Option<&Velocity>,
+        )| {
+            let attributes = attributes3d(
+                &spatial_settings,
+                transform.translation(),
+                velocity.map_or(Vec3::ZERO, |velocity| velocity.current_velocity),
+                *transform.forward(),
+                *transform.up(),
+            );
+
+            sent += 1;
+            if let Err(e) = ffi.call(|| audio_source.set_3d_attributes(attributes)) {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+        };
+
+        if spatial_settings.is_changed() {
+            all_sources.iter().for_each(&mut send);
+        } else {
+            changed_sources.iter().for_each(&mut send);
+        }
+
+        ffi.span().record("sources", sent);
     }
 
     #[deprecated = "Use `AudioSource::get_volume` instead."]
-    pub fn volume(&self) -> f32 {
-        self.get_volume().unwrap().0
+    pub fn volume(&self) -> Result<f32, Error> {
+        Ok(self.get_volume()?.0)
     }
 
     #[deprecated = "Use `AudioSource::set_volume` instead."]
-    pub fn set_volume(&self, volume: f32) {
-        self.event_instance.set_volume(volume).unwrap();
+    pub fn set_volume(&self, volume: f32) -> Result<(), Error> {
+        self.event_instance.set_volume(volume)
     }
 
     #[deprecated = "Use `AudioSource::get_pitch` instead."]
-    pub fn speed(&self) -> f32 {
-        self.get_pitch().unwrap().0
+    pub fn speed(&self) -> Result<f32, Error> {
+        Ok(self.get_pitch()?.0)
     }
 
     #[deprecated = "Use `AudioSource::set_pitch` instead."]
-    pub fn set_speed(&self, speed: f32) {
-        self.set_pitch(speed).unwrap();
+    pub fn set_speed(&self, speed: f32) -> Result<(), Error> {
+        self.set_pitch(speed)
     }
 
     #[deprecated = "Use `AudioSource::start` instead."]
-    pub fn play(&self) {
-        if self.get_paused().unwrap() {
-            self.set_paused(false).unwrap();
+    pub fn play(&self) -> Result<(), Error> {
+        if self.get_paused()? {
+            self.set_paused(false)
         } else {
-            self.start().unwrap();
+            self.start()
         }
     }
 
     #[deprecated = "Use `AudioSource::set_paused(bool)` instead."]
-    pub fn pause(&self) {
-        self.set_paused(true).unwrap();
+    pub fn pause(&self) -> Result<(), Error> {
+        self.set_paused(true)
     }
 
     #[deprecated = "Use `AudioSource::get_paused` instead."]
-    pub fn is_paused(&self) -> bool {
-        self.get_paused().unwrap()
+    pub fn is_paused(&self) -> Result<bool, Error> {
+        self.get_paused()
     }
 
-    pub fn toggle(&self) {
+    pub fn toggle(&self) -> Result<(), Error> {
         self.event_instance
-            .set_paused(!self.event_instance.get_paused().unwrap())
-            .unwrap();
+            .set_paused(!self.event_instance.get_paused()?)
     }
 }
@@ -4,17 +4,35 @@
 //! including audio sources, listeners, and velocity. These components can be used individually or
 //! grouped together using bundles for easier management.
 
+#[doc(hidden)]
+pub mod audio_area;
 #[doc(hidden)]
 pub mod audio_listener;
+#[cfg(feature = "geometry")]
+#[doc(hidden)]
+pub mod audio_occluder;
 #[doc(hidden)]
 pub mod audio_source;
 pub mod bundles;
 #[doc(hidden)]
+pub mod reverb_zone;
+#[doc(hidden)]
+pub mod snapshot;
+#[doc(hidden)]
 pub mod velocity;
 
 #[doc(inline)]
-pub use audio_listener::AudioListener;
+pub use audio_area::{AreaShape, AudioArea, EmitterPosition};
+#[doc(inline)]
+pub use audio_listener::{AudioListener, ListenerAttenuationTarget, ListenerWeight};
+#[cfg(feature = "geometry")]
+#[doc(inline)]
+pub use audio_occluder::AudioOccluder;
+#[doc(inline)]
+pub use audio_source::{AudioSource, InvalidAudioSource, RecreateAudioSource};
+#[doc(inline)]
+pub use reverb_zone::{ReverbPreset, ReverbZone};
 #[doc(inline)]
-pub use audio_source::AudioSource;
+pub use snapshot::Snapshot;
 #[doc(inline)]
-pub use velocity::Velocity;
+pub use velocity::{Velocity, VelocityFromComponent};
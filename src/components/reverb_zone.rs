@@ -0,0 +1,226 @@
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::math::Vec3;
+use bevy::prelude::{
+    Component, DetectChanges, DetectChangesMut, Entity, GlobalTransform, Query, Ref, Res,
+};
+use libfmod::{Error, Reverb3d, ReverbProperties};
+
+use crate::attributes_3d::to_fmod_vec;
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::spatial_settings::SpatialSettings;
+
+/// Component that places a 3D reverb at the entity's position, using
+/// [`System::create_reverb_3d`](libfmod::System::create_reverb_3d).
+///
+/// The reverb is at full strength within [`ReverbZone::min_distance`] and fades out towards
+/// [`ReverbZone::max_distance`]. Where zones overlap, FMOD blends their properties. The reverb
+/// follows the entity's [GlobalTransform] and is released when the component is removed or its
+/// entity despawns.
+///
+/// ```
+/// # use bevy_fmod::components::{ReverbPreset, ReverbZone};
+/// let cave = ReverbZone::new(ReverbPreset::Cave, 5.0, 20.0);
+/// ```
+#[derive(Component)]
+pub struct ReverbZone {
+    /// Distance from the zone's center within which the reverb is at full strength.
+    pub min_distance: f32,
+    /// Distance from the zone's center beyond which the reverb is inaudible.
+    pub max_distance: f32,
+    /// The properties of the reverb, usually taken from a [ReverbPreset].
+    pub properties: ReverbProperties,
+    reverb: Option<Reverb3d>,
+}
+
+impl ReverbZone {
+    /// Creates a reverb zone with the properties of the given preset.
+    #[must_use]
+    pub fn new(preset: ReverbPreset, min_distance: f32, max_distance: f32) -> Self {
+        Self::from_properties(preset.properties(), min_distance, max_distance)
+    }
+
+    /// Creates a reverb zone with custom properties.
+    #[must_use]
+    pub fn from_properties(
+        properties: ReverbProperties,
+        min_distance: f32,
+        max_distance: f32,
+    ) -> Self {
+        ReverbZone {
+            min_distance,
+            max_distance,
+            properties,
+            reverb: None,
+        }
+    }
+
+    /// The FMOD [Reverb3d], once it has been created.
+    pub fn reverb(&self) -> Option<Reverb3d> {
+        self.reverb
+    }
+
+    pub(crate) fn release(&self) -> Result<(), Error> {
+        match self.reverb {
+            Some(reverb) => reverb.release(),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn update_reverbs(
+        mut zones: Query<(Entity, &mut ReverbZone, Ref<GlobalTransform>)>,
+        studio: Res<FmodStudio>,
+        settings: Res<SpatialSettings>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let ffi = FfiScope::new(
+            info_span!("fmod::update_reverbs", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        // Distances are scaled like positions, e.g. by the pixels per meter of the 2D modes
+        let distance_scale = settings.map_vector(Vec3::X).length();
+
+        for (entity, mut zone, transform) in &mut zones {
+            let reverb = match zone.reverb {
+                Some(reverb) if !zone.is_changed() => reverb,
+                Some(reverb) => {
+                    if let Err(e) = ffi.call(|| reverb.set_properties(zone.properties.clone())) {
+                        errors.report(FmodError::new(e).with_entity(entity));
+                    }
+                    reverb
+                }
+                None => {
+                    let result = ffi
+                        .call(|| studio.get_core_system())
+                        .and_then(|system| ffi.call(|| system.create_reverb_3d()))
+                        .and_then(|reverb| {
+                            ffi.call(|| reverb.set_properties(zone.properties.clone()))?;
+                            Ok(reverb)
+                        });
+
+                    match result {
+                        Ok(reverb) => {
+                            zone.bypass_change_detection().reverb = Some(reverb);
+                            reverb
+                        }
+                        Err(e) => {
+                            errors.report(FmodError::new(e).with_entity(entity));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            if !zone.is_changed() && !transform.is_changed() && !settings.is_changed() {
+                continue;
+            }
+
+            if let Err(e) = ffi.call(|| {
+                reverb.set_3d_attributes(
+                    Some(to_fmod_vec(settings.map_vector(transform.translation()))),
+                    zone.min_distance * distance_scale,
+                    zone.max_distance * distance_scale,
+                )
+            }) {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+        }
+    }
+}
+
+/// Reverb properties of typical environments, matching the `FMOD_PRESET_*` macros.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum ReverbPreset {
+    Off,
+    Generic,
+    PaddedCell,
+    Room,
+    Bathroom,
+    LivingRoom,
+    StoneRoom,
+    Auditorium,
+    ConcertHall,
+    Cave,
+    Arena,
+    Hangar,
+    CarpettedHallway,
+    Hallway,
+    StoneCorridor,
+    Alley,
+    Forest,
+    City,
+    Mountains,
+    Quarry,
+    Plain,
+    ParkingLot,
+    SewerPipe,
+    Underwater,
+}
+
+impl ReverbPreset {
+    /// Returns the [ReverbProperties] of the preset.
+    pub fn properties(self) -> ReverbProperties {
+        #[rustfmt::skip]
+        let values = match self {
+            ReverbPreset::Off =>              [1000.0,   7.0,  11.0, 5000.0, 100.0, 100.0, 100.0, 250.0, 0.0,    20.0,  96.0, -80.0],
+            ReverbPreset::Generic =>          [1500.0,   7.0,  11.0, 5000.0,  83.0, 100.0, 100.0, 250.0, 0.0, 14500.0,  96.0,  -8.0],
+            ReverbPreset::PaddedCell =>       [ 170.0,   1.0,   2.0, 5000.0,  10.0, 100.0, 100.0, 250.0, 0.0,   160.0,  84.0,  -7.8],
+            ReverbPreset::Room =>             [ 400.0,   2.0,   3.0, 5000.0,  83.0, 100.0, 100.0, 250.0, 0.0,  6050.0,  88.0,  -9.4],
+            ReverbPreset::Bathroom =>         [1500.0,   7.0,  11.0, 5000.0,  54.0, 100.0,  60.0, 250.0, 0.0,  2900.0,  83.0,   0.5],
+            ReverbPreset::LivingRoom =>       [ 500.0,   3.0,   4.0, 5000.0,  10.0, 100.0, 100.0, 250.0, 0.0,   160.0,  58.0, -19.0],
+            ReverbPreset::StoneRoom =>        [2300.0,  12.0,  17.0, 5000.0,  64.0, 100.0, 100.0, 250.0, 0.0,  7800.0,  71.0,  -8.5],
+            ReverbPreset::Auditorium =>       [4300.0,  20.0,  30.0, 5000.0,  59.0, 100.0, 100.0, 250.0, 0.0,  5850.0,  64.0, -11.7],
+            ReverbPreset::ConcertHall =>      [3900.0,  20.0,  29.0, 5000.0,  70.0, 100.0, 100.0, 250.0, 0.0,  5650.0,  80.0,  -9.8],
+            ReverbPreset::Cave =>             [2900.0,  15.0,  22.0, 5000.0, 100.0, 100.0, 100.0, 250.0, 0.0, 20000.0,  59.0, -11.3],
+            ReverbPreset::Arena =>            [7200.0,  20.0,  30.0, 5000.0,  33.0, 100.0, 100.0, 250.0, 0.0,  4500.0,  80.0,  -9.6],
+            ReverbPreset::Hangar =>           [10000.0, 20.0,  30.0, 5000.0,  23.0, 100.0, 100.0, 250.0, 0.0,  3400.0,  72.0,  -7.4],
+            ReverbPreset::CarpettedHallway => [ 300.0,   2.0,  30.0, 5000.0,  10.0, 100.0, 100.0, 250.0, 0.0,   500.0,  56.0, -24.0],
+            ReverbPreset::Hallway =>          [1500.0,   7.0,  11.0, 5000.0,  59.0, 100.0, 100.0, 250.0, 0.0,  7800.0,  87.0,  -5.5],
+            ReverbPreset::StoneCorridor =>    [ 270.0,  13.0,  20.0, 5000.0,  79.0, 100.0, 100.0, 250.0, 0.0,  9000.0,  86.0,  -6.0],
+            ReverbPreset::Alley =>            [1500.0,   7.0,  11.0, 5000.0,  86.0, 100.0, 100.0, 250.0, 0.0,  8300.0,  80.0,  -9.8],
+            ReverbPreset::Forest =>           [1500.0, 162.0,  88.0, 5000.0,  54.0,  79.0, 100.0, 250.0, 0.0,   760.0,  94.0, -12.3],
+            ReverbPreset::City =>             [1500.0,   7.0,  11.0, 5000.0,  67.0,  50.0, 100.0, 250.0, 0.0,  4050.0,  66.0, -26.0],
+            ReverbPreset::Mountains =>        [1500.0, 300.0, 100.0, 5000.0,  21.0,  27.0, 100.0, 250.0, 0.0,  1220.0,  82.0, -24.0],
+            ReverbPreset::Quarry =>           [1500.0,  61.0,  25.0, 5000.0,  83.0, 100.0, 100.0, 250.0, 0.0,  3400.0, 100.0,  -5.0],
+            ReverbPreset::Plain =>            [1500.0, 179.0, 100.0, 5000.0,  50.0,  21.0, 100.0, 250.0, 0.0,  1670.0,  65.0, -28.0],
+            ReverbPreset::ParkingLot =>       [1700.0,   8.0,  12.0, 5000.0, 100.0, 100.0, 100.0, 250.0, 0.0, 20000.0,  56.0, -19.5],
+            ReverbPreset::SewerPipe =>        [2800.0,  14.0,  21.0, 5000.0,  14.0,  80.0,  60.0, 250.0, 0.0,  3400.0,  66.0,   1.2],
+            ReverbPreset::Underwater =>       [1500.0,   7.0,  11.0, 5000.0,  10.0, 100.0, 100.0, 250.0, 0.0,   500.0,  92.0,   7.0],
+        };
+
+        let [
+            decay_time,
+            early_delay,
+            late_delay,
+            hf_reference,
+            hf_decay_ratio,
+            diffusion,
+            density,
+            low_shelf_frequency,
+            low_shelf_gain,
+            high_cut,
+            early_late_mix,
+            wet_level,
+        ] = values;
+
+        ReverbProperties {
+            decay_time,
+            early_delay,
+            late_delay,
+            hf_reference,
+            hf_decay_ratio,
+            diffusion,
+            density,
+            low_shelf_frequency,
+            low_shelf_gain,
+            high_cut,
+            early_late_mix,
+            wet_level,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::prelude::{Component, Deref, DerefMut, Entity, Query, Real, Res, Time};
+use libfmod::{Error, EventDescription, EventInstance, StopMode, Studio};
+
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::profiling::{FfiScope, FmodFfiCalls};
+
+/// Component that keeps an FMOD snapshot active for as long as it exists.
+///
+/// The snapshot starts when the component is added and stops with [`Snapshot::stop_mode`] when
+/// it is removed or its entity despawns.
+///
+/// The intensity of a snapshot can be faded with [`Snapshot::fade_to`]. This requires the
+/// snapshot's intensity to be exposed as a parameter in FMOD Studio, named
+/// [`Snapshot::intensity_parameter`]. Fades use real time, so they keep running while
+/// [`Time<Virtual>`](bevy::time::Virtual) is paused.
+#[derive(Component, Deref, DerefMut)]
+pub struct Snapshot {
+    /// The [EventInstance] of the snapshot.
+    #[deref]
+    pub event_instance: EventInstance,
+    /// The [StopMode] to use when the component is removed.
+    pub stop_mode: StopMode,
+    /// Name of the parameter that controls the snapshot intensity. Defaults to `"Intensity"`.
+    pub intensity_parameter: &'static str,
+    intensity: f32,
+    target_intensity: f32,
+    fade_rate: f32,
+    dirty: bool,
+}
+
+impl Snapshot {
+    /// The intensity of a snapshot at full effect, in percent.
+    pub const FULL_INTENSITY: f32 = 100.0;
+
+    /// Creates a snapshot from its [EventDescription].
+    pub fn new(description: EventDescription) -> Result<Self, Error> {
+        Ok(Snapshot {
+            event_instance: description.create_instance()?,
+            stop_mode: StopMode::AllowFadeout,
+            intensity_parameter: "Intensity",
+            intensity: Self::FULL_INTENSITY,
+            target_intensity: Self::FULL_INTENSITY,
+            fade_rate: 0.0,
+            dirty: false,
+        })
+    }
+
+    /// Creates a snapshot from its path, e.g. `snapshot:/Underwater`.
+    pub fn from_path(studio: &Studio, path: &str) -> Result<Self, Error> {
+        Self::new(studio.get_event(path)?)
+    }
+
+    /// Sets the [StopMode] to use when the component is removed.
+    #[must_use]
+    pub fn with_stop_mode(mut self, stop_mode: StopMode) -> Self {
+        self.stop_mode = stop_mode;
+        self
+    }
+
+    /// Sets the initial intensity, in percent.
+    #[must_use]
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self.target_intensity = intensity;
+        self.dirty = true;
+        self
+    }
+
+    /// The current intensity of the snapshot, in percent.
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    /// Fades the intensity to `intensity` (in percent) over `duration`.
+    pub fn fade_to(&mut self, intensity: f32, duration: Duration) {
+        self.target_intensity = intensity;
+        self.fade_rate = (intensity - self.intensity).abs() / duration.as_secs_f32();
+        self.dirty = true;
+    }
+
+    pub(crate) fn update_intensity(
+        mut query: Query<(Entity, &mut Snapshot)>,
+        time: Res<Time<Real>>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let ffi = FfiScope::new(
+            info_span!("fmod::update_snapshot_intensity", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        query
+            .iter_mut()
+            .filter(|(_, snapshot)| snapshot.dirty)
+            .for_each(|(entity, mut snapshot)| {
+                let step = snapshot.fade_rate * time.delta_secs();
+                let difference = snapshot.target_intensity - snapshot.intensity;
+
+                if !step.is_finite() || difference.abs() <= step {
+                    snapshot.intensity = snapshot.target_intensity;
+                    snapshot.dirty = false;
+                } else {
+                    snapshot.intensity += step.copysign(difference);
+                }
+
+                if let Err(e) = ffi.call(|| {
+                    snapshot.event_instance.set_parameter_by_name(
+                        snapshot.intensity_parameter,
+                        snapshot.intensity,
+                        true,
+                    )
+                }) {
+                    errors.report(FmodError::new(e).with_entity(entity));
+                    snapshot.dirty = false;
+                }
+            });
+    }
+}
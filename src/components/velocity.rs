@@ -1,6 +1,14 @@
-use bevy::app::{App, Plugin, Update};
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::log::info_span;
 use bevy::math::Vec3;
-use bevy::prelude::{Component, GlobalTransform, Local, Query, Res, Time};
+use bevy::prelude::{
+    Component, DetectChangesMut, GlobalTransform, IntoScheduleConfigs, Query, Res, Time,
+};
+
+use crate::fmod_plugin::FmodSystems;
+use crate::spatial_settings::SpatialSettings;
 
 /// Automatic velocity updates for
 /// [`AudioListener`](crate::components::audio_listener::AudioListener) and
@@ -10,10 +18,109 @@ use bevy::prelude::{Component, GlobalTransform, Local, Query, Res, Time};
 /// to enable the Doppler effect. The recommended way to do this is to use the
 /// [`SpatialAudioBundle`](crate::components::bundles::SpatialAudioBundle) and
 /// [`SpatialListenerBundle`](crate::components::bundles::SpatialListenerBundle).
-#[derive(Component, Default)]
+///
+/// By default, the velocity is computed from the movement of the entity's [GlobalTransform].
+/// Alternatively, it can be set manually with [`Velocity::set`] or taken from a physics engine
+/// with [VelocityFromComponent].
+///
+/// Movements faster than [`Velocity::DEFAULT_TELEPORT_SPEED`] are treated as teleports, see
+/// [`Velocity::with_teleport_speed`].
+#[derive(Component)]
 pub struct Velocity {
-    last_position: Vec3,
+    last_position: Option<Vec3>,
+    target_velocity: Vec3,
     pub(crate) current_velocity: Vec3,
+    manual: bool,
+    smoothing: f32,
+    teleport_speed: f32,
+}
+
+impl Default for Velocity {
+    fn default() -> Self {
+        Velocity {
+            last_position: None,
+            target_velocity: Vec3::ZERO,
+            current_velocity: Vec3::ZERO,
+            manual: false,
+            smoothing: 0.0,
+            teleport_speed: Velocity::DEFAULT_TELEPORT_SPEED,
+        }
+    }
+}
+
+impl Velocity {
+    /// The default speed in meters per second above which a movement is treated as a teleport.
+    /// Roughly three times the speed of sound.
+    pub const DEFAULT_TELEPORT_SPEED: f32 = 1000.0;
+
+    /// Creates a velocity that is only changed through [`Velocity::set`].
+    #[must_use]
+    pub fn manual(velocity: Vec3) -> Self {
+        let mut manual = Velocity::default();
+        manual.set(velocity);
+        manual
+    }
+
+    /// Smooths velocity changes over roughly `seconds`, which avoids Doppler spikes when the
+    /// entity changes direction abruptly. Defaults to `0.0`, which disables smoothing.
+    #[must_use]
+    pub fn with_smoothing(mut self, seconds: f32) -> Self {
+        self.smoothing = seconds.max(0.0);
+        self
+    }
+
+    /// Treats movements faster than `speed` meters per second as teleports, which reset the
+    /// velocity to zero instead of producing a huge Doppler shift. The speed is measured in FMOD
+    /// space, after the [SpatialSettings] mapping, so it doesn't depend on the units of the world,
+    /// e.g. centimeters or pixels. Defaults to
+    /// [`Velocity::DEFAULT_TELEPORT_SPEED`], use [`f32::INFINITY`] to disable teleport detection.
+    #[must_use]
+    pub fn with_teleport_speed(mut self, speed: f32) -> Self {
+        self.teleport_speed = speed;
+        self
+    }
+
+    /// The velocity that is currently passed to FMOD.
+    pub fn current(&self) -> Vec3 {
+        self.current_velocity
+    }
+
+    /// Sets the velocity manually. From then on, the velocity is no longer computed from the
+    /// entity's movement, but smoothing and teleport detection still apply.
+    pub fn set(&mut self, velocity: Vec3) {
+        self.manual = true;
+        self.target_velocity = velocity;
+    }
+
+    /// Switches back to computing the velocity from the entity's movement.
+    pub fn set_automatic(&mut self) {
+        self.manual = false;
+    }
+
+    fn update(&mut self, position: Vec3, delta_time: f32, settings: &SpatialSettings) {
+        let delta_position = self
+            .last_position
+            .map_or(Vec3::ZERO, |last_position| position - last_position);
+        self.last_position = Some(position);
+
+        if settings.map_vector(delta_position).length() > self.teleport_speed * delta_time {
+            self.target_velocity = Vec3::ZERO;
+            self.current_velocity = Vec3::ZERO;
+            return;
+        }
+
+        if !self.manual {
+            self.target_velocity = delta_position / delta_time;
+        }
+
+        let blend = if self.smoothing > 0.0 {
+            1.0 - (-delta_time / self.smoothing).exp()
+        } else {
+            1.0
+        };
+
+        self.current_velocity = self.current_velocity.lerp(self.target_velocity, blend);
+    }
 }
 
 pub(crate) struct VelocityPlugin;
@@ -22,27 +129,78 @@ impl VelocityPlugin {
     fn update_velocity(
         mut velocity: Query<(&mut Velocity, &GlobalTransform)>,
         time: Res<Time>,
-        mut last_delta: Local<f32>,
+        settings: Res<SpatialSettings>,
     ) {
-        let delta_time = *last_delta;
-        *last_delta = time.delta_secs();
+        let delta_time = time.delta_secs();
 
         if delta_time == 0.0 {
             return;
         }
 
+        let _span = info_span!("fmod::update_velocities").entered();
+
         velocity.iter_mut().for_each(|(mut velocity, transform)| {
-            let current_position = transform.translation();
-            let delta_position = current_position - velocity.last_position;
+            // Only flag velocities that actually changed, so static sources are not updated
+            let previous_velocity = velocity.current_velocity;
+            velocity.bypass_change_detection().update(
+                transform.translation(),
+                delta_time,
+                &settings,
+            );
 
-            velocity.current_velocity = delta_position / delta_time;
-            velocity.last_position = current_position;
+            if velocity.current_velocity != previous_velocity {
+                velocity.set_changed();
+            }
         });
     }
 }
 
 impl Plugin for VelocityPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, Self::update_velocity);
+        app.add_systems(
+            PostUpdate,
+            Self::update_velocity.in_set(FmodSystems::Velocity),
+        );
+    }
+}
+
+/// Takes the [Velocity] of entities from another component, e.g. the linear velocity of a
+/// physics engine, instead of computing it from their movement.
+///
+/// ```ignore
+/// app.add_plugins(VelocityFromComponent::<LinearVelocity>::new(|velocity| velocity.0));
+/// ```
+pub struct VelocityFromComponent<C: Component> {
+    get_velocity: fn(&C) -> Vec3,
+    _component: PhantomData<C>,
+}
+
+impl<C: Component> VelocityFromComponent<C> {
+    /// Reads the velocity from `C` using `get_velocity`.
+    #[must_use]
+    pub fn new(get_velocity: fn(&C) -> Vec3) -> Self {
+        VelocityFromComponent {
+            get_velocity,
+            _component: PhantomData,
+        }
+    }
+}
+
+impl<C: Component> Plugin for VelocityFromComponent<C> {
+    fn build(&self, app: &mut App) {
+        let get_velocity = self.get_velocity;
+
+        app.add_systems(
+            PostUpdate,
+            (move |mut query: Query<(&mut Velocity, &C)>| {
+                query.iter_mut().for_each(|(mut velocity, component)| {
+                    velocity
+                        .bypass_change_detection()
+                        .set(get_velocity(component));
+                });
+            })
+            .before(VelocityPlugin::update_velocity)
+            .in_set(FmodSystems::Velocity),
+        );
     }
 }
@@ -0,0 +1,179 @@
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::color::palettes::css::{GRAY, LIME, ORANGE, YELLOW};
+use bevy::color::{Alpha, Color};
+use bevy::gizmos::config::{GizmoConfigGroup, GizmoConfigStore};
+use bevy::gizmos::{AppGizmoBuilder, gizmos::Gizmos};
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::math::{Isometry3d, Vec3};
+use bevy::prelude::{GlobalTransform, IntoScheduleConfigs, Query, Res, With};
+use bevy::reflect::Reflect;
+use libfmod::PlaybackState;
+
+use crate::components::audio_area::EmitterPosition;
+use crate::components::audio_listener::AudioListener;
+use crate::components::audio_source::AudioSource;
+use crate::components::velocity::Velocity;
+use crate::fmod_plugin::FmodSystems;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::spatial_settings::SpatialSettings;
+
+/// Draws [AudioSource]s, their attenuation ranges and velocities, and the orientation of
+/// [AudioListener]s with Bevy [Gizmos].
+///
+/// Drawing is configured and toggled at runtime through the [FmodGizmos] config group:
+///
+/// ```ignore
+/// fn toggle_audio_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+///     let (config, _) = config_store.config_mut::<FmodGizmos>();
+///     config.enabled = !config.enabled;
+/// }
+/// ```
+pub struct FmodDebugGizmosPlugin;
+
+/// The [GizmoConfigGroup] of the [FmodDebugGizmosPlugin].
+#[derive(Reflect, GizmoConfigGroup, Clone, Debug)]
+pub struct FmodGizmos {
+    /// Whether to draw the min and max distance of every source as spheres.
+    pub attenuation: bool,
+    /// Whether to draw the velocity of sources and listeners as arrows.
+    pub velocity: bool,
+    /// Whether to draw the orientation of listeners as axes.
+    pub listeners: bool,
+    /// Color of sources that are playing.
+    pub playing_color: Color,
+    /// Color of sources that are stopped or paused.
+    pub stopped_color: Color,
+    /// Color of listeners.
+    pub listener_color: Color,
+    /// Color of velocity arrows.
+    pub velocity_color: Color,
+}
+
+impl Default for FmodGizmos {
+    fn default() -> Self {
+        FmodGizmos {
+            attenuation: true,
+            velocity: true,
+            listeners: true,
+            playing_color: LIME.into(),
+            stopped_color: GRAY.into(),
+            listener_color: YELLOW.into(),
+            velocity_color: ORANGE.into(),
+        }
+    }
+}
+
+impl Plugin for FmodDebugGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_gizmo_group::<FmodGizmos>().add_systems(
+            PostUpdate,
+            (draw_sources, draw_listeners).after(FmodSystems::Spatial),
+        );
+    }
+}
+
+fn draw_sources(
+    mut gizmos: Gizmos<FmodGizmos>,
+    config_store: Res<GizmoConfigStore>,
+    sources: Query<(
+        &AudioSource,
+        &GlobalTransform,
+        Option<&EmitterPosition>,
+        Option<&Velocity>,
+    )>,
+    spatial_settings: Res<SpatialSettings>,
+    ffi_calls: Res<FmodFfiCalls>,
+) {
+    let (gizmo_config, config) = config_store.config::<FmodGizmos>();
+
+    // Skip the FMOD queries below while the gizmos are hidden
+    if !gizmo_config.enabled {
+        return;
+    }
+
+    let ffi = FfiScope::new(
+        info_span!("fmod::draw_sources", ffi_calls = Empty),
+        &ffi_calls,
+    );
+
+    // Min and max distances are in FMOD units
+    let distance_scale = spatial_settings.map_vector(Vec3::X).length();
+
+    for (audio_source, transform, emitter, velocity) in &sources {
+        let position = emitter.map_or(transform.translation(), |emitter| emitter.0);
+
+        let playing = matches!(
+            ffi.call(|| audio_source.get_playback_state()),
+            Ok(PlaybackState::Playing | PlaybackState::Starting | PlaybackState::Sustaining)
+        ) && !ffi.call(|| audio_source.get_paused()).unwrap_or(false);
+        let color = if playing {
+            config.playing_color
+        } else {
+            config.stopped_color
+        };
+
+        gizmos.sphere(Isometry3d::from_translation(position), 0.1, color);
+
+        if config.attenuation
+            && let Ok((min, max)) = ffi
+                .call(|| audio_source.get_description())
+                .and_then(|description| ffi.call(|| description.get_min_max_distance()))
+        {
+            let isometry = Isometry3d::from_translation(position);
+            gizmos.sphere(isometry, min / distance_scale, color);
+            gizmos.sphere(isometry, max / distance_scale, color.with_alpha(0.3));
+        }
+
+        if config.velocity
+            && let Some(velocity) = velocity
+        {
+            gizmos.arrow(
+                position,
+                position + velocity.current(),
+                config.velocity_color,
+            );
+        }
+    }
+}
+
+fn draw_listeners(
+    mut gizmos: Gizmos<FmodGizmos>,
+    config_store: Res<GizmoConfigStore>,
+    listeners: Query<(&GlobalTransform, Option<&Velocity>), With<AudioListener>>,
+) {
+    let (gizmo_config, config) = config_store.config::<FmodGizmos>();
+
+    if !gizmo_config.enabled {
+        return;
+    }
+
+    for (transform, velocity) in &listeners {
+        let position = transform.translation();
+
+        gizmos.sphere(
+            Isometry3d::from_translation(position),
+            0.1,
+            config.listener_color,
+        );
+
+        if config.listeners {
+            gizmos.axes(*transform, 0.5);
+            gizmos.arrow(
+                position,
+                position + *transform.forward(),
+                config.listener_color,
+            );
+        }
+
+        if config.velocity
+            && let Some(velocity) = velocity
+        {
+            gizmos.arrow(
+                position,
+                position + velocity.current(),
+                config.velocity_color,
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+
+use bevy::log::{debug, error, info, trace, warn};
+use libfmod::ffi::{
+    FMOD_DEBUG_CALLBACK, FMOD_DEBUG_FLAGS, FMOD_DEBUG_LEVEL_ERROR, FMOD_DEBUG_LEVEL_LOG,
+    FMOD_DEBUG_LEVEL_WARNING, FMOD_DEBUG_TYPE_CODEC, FMOD_DEBUG_TYPE_FILE, FMOD_DEBUG_TYPE_MEMORY,
+    FMOD_DEBUG_TYPE_TRACE, FMOD_OK, FMOD_RESULT,
+};
+use libfmod::{Debug as FmodDebug, DebugMode};
+
+/// The most verbose level of FMOD debug output that is logged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FmodDebugLevel {
+    /// Only errors, logged as [`error!`].
+    Error,
+    /// Errors and warnings, logged as [`warn!`].
+    #[default]
+    Warning,
+    /// Errors, warnings and informational messages, logged as [`info!`]. Messages of the verbose
+    /// categories are logged as [`debug!`], or [`trace!`] for [`FmodDebugLog::trace`].
+    Log,
+}
+
+/// Forwards the debug output of FMOD into `bevy::log`, with the `fmod` target. Enable it with
+/// [`FmodPlugin::with_debug_log`](crate::FmodPlugin::with_debug_log).
+///
+/// FMOD only produces debug output when the logging version of its libraries (`fmodL` and
+/// `fmodstudioL`) is linked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FmodDebugLog {
+    /// The most verbose level that is logged.
+    pub level: FmodDebugLevel,
+    /// Whether to log memory allocations.
+    pub memory: bool,
+    /// Whether to log file access.
+    pub file: bool,
+    /// Whether to log codec initialization.
+    pub codec: bool,
+    /// Whether to log FMOD's verbose internal trace output, which shows where internal error
+    /// codes come from. Only has an effect with [FmodDebugLevel::Log].
+    pub trace: bool,
+}
+
+impl FmodDebugLog {
+    /// Logs messages up to the given level, without any of the verbose categories.
+    #[must_use]
+    pub fn new(level: FmodDebugLevel) -> Self {
+        FmodDebugLog {
+            level,
+            ..Default::default()
+        }
+    }
+
+    fn flags(&self) -> FMOD_DEBUG_FLAGS {
+        let mut flags = match self.level {
+            FmodDebugLevel::Error => FMOD_DEBUG_LEVEL_ERROR,
+            FmodDebugLevel::Warning => FMOD_DEBUG_LEVEL_WARNING,
+            FmodDebugLevel::Log => FMOD_DEBUG_LEVEL_LOG,
+        };
+
+        for (enabled, flag) in [
+            (self.memory, FMOD_DEBUG_TYPE_MEMORY),
+            (self.file, FMOD_DEBUG_TYPE_FILE),
+            (self.codec, FMOD_DEBUG_TYPE_CODEC),
+            (self.trace, FMOD_DEBUG_TYPE_TRACE),
+        ] {
+            if enabled {
+                flags |= flag;
+            }
+        }
+
+        flags
+    }
+
+    /// The callback that forwards FMOD debug output into `bevy::log`, for calling
+    /// [`Debug::initialize`](libfmod::Debug::initialize) yourself.
+    pub fn callback() -> FMOD_DEBUG_CALLBACK {
+        Some(forward)
+    }
+
+    /// Must be called before FMOD Studio is created to catch messages of its initialization.
+    pub(crate) fn initialize(&self) {
+        if let Err(e) =
+            FmodDebug::initialize(self.flags(), DebugMode::Callback, Self::callback(), None)
+        {
+            warn!(
+                "Failed to initialize FMOD debug logging, which requires the logging version of \
+                 the FMOD libraries: {}",
+                e
+            );
+        }
+    }
+}
+
+unsafe extern "C" fn forward(
+    flags: FMOD_DEBUG_FLAGS,
+    file: *const c_char,
+    line: c_int,
+    function: *const c_char,
+    message: *const c_char,
+) -> FMOD_RESULT {
+    let file = unsafe { to_str(file) };
+    let function = unsafe { to_str(function) };
+    let message = unsafe { to_str(message) };
+    let message = message.trim_end();
+
+    if flags & FMOD_DEBUG_LEVEL_ERROR != 0 {
+        error!(target: "fmod", "{}:{} ({}) {}", file, line, function, message);
+    } else if flags & FMOD_DEBUG_LEVEL_WARNING != 0 {
+        warn!(target: "fmod", "{}:{} ({}) {}", file, line, function, message);
+    } else if flags & FMOD_DEBUG_TYPE_TRACE != 0 {
+        trace!(target: "fmod", "{}:{} ({}) {}", file, line, function, message);
+    } else if flags & (FMOD_DEBUG_TYPE_MEMORY | FMOD_DEBUG_TYPE_FILE | FMOD_DEBUG_TYPE_CODEC) != 0 {
+        debug!(target: "fmod", "{}:{} ({}) {}", file, line, function, message);
+    } else {
+        info!(target: "fmod", "{}:{} ({}) {}", file, line, function, message);
+    }
+
+    FMOD_OK
+}
+
+/// # Safety
+///
+/// `string` must be null or point to a null-terminated string that outlives the returned value.
+unsafe fn to_str<'a>(string: *const c_char) -> Cow<'a, str> {
+    if string.is_null() {
+        return "".into();
+    }
+
+    unsafe { CStr::from_ptr(string) }.to_string_lossy()
+}
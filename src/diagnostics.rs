@@ -0,0 +1,193 @@
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::{IntoScheduleConfigs, Local, Res};
+use libfmod::{BufferInfo, Error, Memory};
+
+use crate::fmod_error::FmodErrorReporter;
+use crate::fmod_plugin::FmodSystems;
+use crate::fmod_studio::FmodStudio;
+use crate::profiling::FmodFfiCalls;
+
+/// Adds FMOD performance metrics as Bevy [Diagnostic]s, so they show up in the
+/// [`LogDiagnosticsPlugin`](bevy::diagnostic::LogDiagnosticsPlugin) and performance overlays
+/// next to the frame time.
+///
+/// The diagnostics are measured once per frame, after FMOD Studio has been updated.
+pub struct FmodDiagnosticsPlugin;
+
+impl FmodDiagnosticsPlugin {
+    /// CPU usage of the FMOD Studio update, in percent.
+    pub const STUDIO_CPU: DiagnosticPath = DiagnosticPath::const_new("fmod/cpu/studio");
+    /// CPU usage of the DSP mixer, in percent.
+    pub const DSP_CPU: DiagnosticPath = DiagnosticPath::const_new("fmod/cpu/dsp");
+    /// CPU usage of streams, in percent.
+    pub const STREAM_CPU: DiagnosticPath = DiagnosticPath::const_new("fmod/cpu/stream");
+    /// CPU usage of geometry occlusion, in percent.
+    pub const GEOMETRY_CPU: DiagnosticPath = DiagnosticPath::const_new("fmod/cpu/geometry");
+    /// CPU usage of the FMOD Core update, in percent.
+    pub const UPDATE_CPU: DiagnosticPath = DiagnosticPath::const_new("fmod/cpu/update");
+    /// Memory currently allocated by FMOD, in MiB.
+    pub const MEMORY: DiagnosticPath = DiagnosticPath::const_new("fmod/memory/current");
+    /// The most memory FMOD has allocated at once, in MiB.
+    pub const MEMORY_PEAK: DiagnosticPath = DiagnosticPath::const_new("fmod/memory/peak");
+    /// Memory used by loaded sample data, in MiB.
+    pub const SAMPLE_DATA_MEMORY: DiagnosticPath =
+        DiagnosticPath::const_new("fmod/memory/sample_data");
+    /// Number of channels playing, including virtual ones.
+    pub const CHANNELS: DiagnosticPath = DiagnosticPath::const_new("fmod/channels/playing");
+    /// Number of channels that are actually audible.
+    pub const REAL_CHANNELS: DiagnosticPath = DiagnosticPath::const_new("fmod/channels/real");
+    /// Usage of the Studio command queue, in percent of its capacity.
+    pub const COMMAND_QUEUE: DiagnosticPath =
+        DiagnosticPath::const_new("fmod/buffers/command_queue");
+    /// Usage of the Studio handle table, in percent of its capacity.
+    pub const HANDLES: DiagnosticPath = DiagnosticPath::const_new("fmod/buffers/handles");
+    /// Number of times the command queue was full, since the last frame.
+    pub const COMMAND_QUEUE_STALLS: DiagnosticPath =
+        DiagnosticPath::const_new("fmod/buffers/command_queue_stalls");
+    /// Number of FFI calls made in the last frame, see [FmodFfiCalls].
+    pub const FFI_CALLS: DiagnosticPath = DiagnosticPath::const_new("fmod/ffi_calls");
+
+    fn measure(
+        mut diagnostics: Diagnostics,
+        mut state: Local<MeasureState>,
+        studio: Res<FmodStudio>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        diagnostics.add_measurement(&Self::FFI_CALLS, || ffi_calls.last_frame as f64);
+
+        if let Some((studio_usage, core_usage)) =
+            state.check(&mut errors, "FMOD CPU usage", studio.get_cpu_usage())
+        {
+            diagnostics.add_measurement(&Self::STUDIO_CPU, || studio_usage.update as f64);
+            diagnostics.add_measurement(&Self::DSP_CPU, || core_usage.dsp as f64);
+            diagnostics.add_measurement(&Self::STREAM_CPU, || core_usage.stream as f64);
+            diagnostics.add_measurement(&Self::GEOMETRY_CPU, || core_usage.geometry as f64);
+            diagnostics.add_measurement(&Self::UPDATE_CPU, || core_usage.update as f64);
+        }
+
+        if let Some((current, peak)) =
+            state.check(&mut errors, "FMOD memory stats", Memory::get_stats(false))
+        {
+            diagnostics.add_measurement(&Self::MEMORY, || mebibytes(current));
+            diagnostics.add_measurement(&Self::MEMORY_PEAK, || mebibytes(peak));
+        }
+
+        if let Some(usage) = state.check(
+            &mut errors,
+            "FMOD Studio memory usage",
+            studio.get_memory_usage(),
+        ) {
+            diagnostics.add_measurement(&Self::SAMPLE_DATA_MEMORY, || mebibytes(usage.sampledata));
+        }
+
+        if let Some((channels, real_channels)) = state.check(
+            &mut errors,
+            "FMOD channels playing",
+            studio
+                .get_core_system()
+                .and_then(|system| system.get_channels_playing()),
+        ) {
+            diagnostics.add_measurement(&Self::CHANNELS, || channels as f64);
+            diagnostics.add_measurement(&Self::REAL_CHANNELS, || real_channels as f64);
+        }
+
+        if let Some(usage) =
+            state.check(&mut errors, "FMOD buffer usage", studio.get_buffer_usage())
+        {
+            let command_queue = usage.studiocommandqueue;
+            let stalls = state.stalls_since_last_frame(command_queue.stallcount);
+
+            diagnostics.add_measurement(&Self::COMMAND_QUEUE, || percent(&command_queue));
+            diagnostics.add_measurement(&Self::HANDLES, || percent(&usage.studiohandle));
+            diagnostics.add_measurement(&Self::COMMAND_QUEUE_STALLS, || stalls as f64);
+        }
+    }
+}
+
+#[derive(Default)]
+struct MeasureState {
+    stall_count: Option<i32>,
+    failing: HashSet<&'static str>,
+}
+
+impl MeasureState {
+    /// Reports a failure only once until the metric can be measured again, so an unsupported
+    /// metric doesn't flood the log every frame.
+    fn check<T>(
+        &mut self,
+        errors: &mut FmodErrorReporter,
+        metric: &'static str,
+        result: Result<T, Error>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.failing.remove(metric);
+                Some(value)
+            }
+            Err(e) => {
+                if self.failing.insert(metric) {
+                    errors.report(e);
+                }
+                None
+            }
+        }
+    }
+
+    /// FMOD accumulates the stall count until the buffer usage is reset, which also resets the
+    /// peak usage, so the difference to the last frame is taken instead.
+    fn stalls_since_last_frame(&mut self, stall_count: i32) -> i32 {
+        let stalls = match self.stall_count {
+            Some(last) if stall_count >= last => stall_count - last,
+            // Reset elsewhere
+            _ => stall_count,
+        };
+        self.stall_count = Some(stall_count);
+        stalls
+    }
+}
+
+fn mebibytes(bytes: i32) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+fn percent(buffer: &BufferInfo) -> f64 {
+    if buffer.capacity > 0 {
+        buffer.currentusage as f64 / buffer.capacity as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+impl Plugin for FmodDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        for path in [
+            Self::STUDIO_CPU,
+            Self::DSP_CPU,
+            Self::STREAM_CPU,
+            Self::GEOMETRY_CPU,
+            Self::UPDATE_CPU,
+            Self::COMMAND_QUEUE,
+            Self::HANDLES,
+        ] {
+            app.register_diagnostic(Diagnostic::new(path).with_suffix("%"));
+        }
+
+        for path in [Self::MEMORY, Self::MEMORY_PEAK, Self::SAMPLE_DATA_MEMORY] {
+            app.register_diagnostic(Diagnostic::new(path).with_suffix(" MiB"));
+        }
+
+        for path in [
+            Self::CHANNELS,
+            Self::REAL_CHANNELS,
+            Self::COMMAND_QUEUE_STALLS,
+            Self::FFI_CALLS,
+        ] {
+            app.register_diagnostic(Diagnostic::new(path));
+        }
+
+        app.add_systems(PostUpdate, Self::measure.after(FmodSystems::Update));
+    }
+}
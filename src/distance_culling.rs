@@ -0,0 +1,253 @@
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::prelude::{
+    Component, DetectChanges, Entity, GlobalTransform, IntoScheduleConfigs, Query, Ref, Res,
+    Resource, With, Without,
+};
+use libfmod::{Error, EventInstance, PlaybackState, StopMode};
+
+use crate::components::audio_area::{AudioArea, EmitterPosition};
+use crate::components::audio_listener::AudioListener;
+use crate::components::audio_source::{AudioSource, InvalidAudioSource};
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::fmod_plugin::FmodSystems;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::spatial_settings::SpatialSettings;
+
+/// How sources beyond their event's max distance are silenced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CullingMode {
+    /// Pauses the event instance. It keeps its voices and resources, but FMOD stops processing
+    /// it.
+    #[default]
+    Pause,
+    /// Stops the event instance, which frees its voices and resources. When the source is
+    /// restored, it is restarted at the timeline position it was stopped at, unless it was
+    /// started again in the meantime or [DistanceCulling::keep_stopped] was called.
+    Stop,
+}
+
+/// Settings of the [DistanceCullingPlugin]. Changes take effect in the next frame.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct DistanceCullingSettings {
+    /// How culled sources are silenced.
+    pub mode: CullingMode,
+    /// Extra distance, as a fraction of the max distance, that a source has to move beyond the
+    /// max distance before it is culled. Sources are restored as soon as they are within the max
+    /// distance again, so sources near the edge don't toggle every frame.
+    pub hysteresis: f32,
+}
+
+impl Default for DistanceCullingSettings {
+    fn default() -> Self {
+        DistanceCullingSettings {
+            mode: CullingMode::default(),
+            hysteresis: 0.1,
+        }
+    }
+}
+
+/// Culling state of an [AudioSource]. Added to all sources by the [DistanceCullingPlugin].
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct DistanceCulling {
+    max_distance: Option<f32>,
+    culled: Option<Culled>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Culled {
+    /// The source wasn't playing when it went out of range, so it isn't checked again until it
+    /// is back in range.
+    Idle,
+    Paused,
+    Stopped {
+        timeline_position: i32,
+    },
+}
+
+impl DistanceCulling {
+    /// Whether the source is currently silenced because it is out of range of all listeners.
+    pub fn is_culled(&self) -> bool {
+        matches!(self.culled, Some(Culled::Paused | Culled::Stopped { .. }))
+    }
+
+    /// Keeps a source that was culled with [CullingMode::Stop] stopped when it is back in range,
+    /// e.g. because the game stopped it while it was culled.
+    pub fn keep_stopped(&mut self) {
+        if let Some(Culled::Stopped { .. }) = self.culled {
+            self.culled = Some(Culled::Idle);
+        }
+    }
+}
+
+/// Marker for [AudioSource]s that are never culled, e.g. music and UI sounds.
+#[derive(Component, Default)]
+pub struct NeverCull;
+
+/// Silences playing [AudioSource]s that are further away from all [AudioListener]s than the max
+/// distance of their event, as set in FMOD Studio, and restores them when a listener approaches.
+///
+/// This keeps open worlds with hundreds of ambient sources cheap. Only 3D events are culled.
+/// Sources that aren't playing when they go out of range are only checked again once they are
+/// back in range, so starting a source far away from all listeners doesn't silence it.
+pub struct DistanceCullingPlugin {
+    settings: DistanceCullingSettings,
+}
+
+impl DistanceCullingPlugin {
+    /// Creates the plugin with the given settings.
+    #[must_use]
+    pub fn new(settings: DistanceCullingSettings) -> Self {
+        DistanceCullingPlugin { settings }
+    }
+}
+
+impl Default for DistanceCullingPlugin {
+    fn default() -> Self {
+        Self::new(DistanceCullingSettings::default())
+    }
+}
+
+impl Plugin for DistanceCullingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.settings.clone())
+            .register_required_components::<AudioSource, DistanceCulling>()
+            .add_systems(
+                PostUpdate,
+                cull_by_distance
+                    .after(AudioArea::update_3d_attributes)
+                    .in_set(FmodSystems::Spatial),
+            );
+    }
+}
+
+fn max_distance(ffi: &FfiScope, event_instance: EventInstance) -> Result<f32, Error> {
+    let description = ffi.call(|| event_instance.get_description())?;
+
+    if ffi.call(|| description.is_3d())? {
+        Ok(ffi.call(|| description.get_min_max_distance())?.1)
+    } else {
+        Ok(f32::INFINITY)
+    }
+}
+
+fn cull(ffi: &FfiScope, event_instance: EventInstance, mode: CullingMode) -> Result<Culled, Error> {
+    let playing = matches!(
+        ffi.call(|| event_instance.get_playback_state())?,
+        PlaybackState::Playing | PlaybackState::Starting | PlaybackState::Sustaining
+    );
+
+    // Sources that were stopped or paused by the user are left alone
+    if !playing || ffi.call(|| event_instance.get_paused())? {
+        return Ok(Culled::Idle);
+    }
+
+    match mode {
+        CullingMode::Pause => {
+            ffi.call(|| event_instance.set_paused(true))?;
+            Ok(Culled::Paused)
+        }
+        CullingMode::Stop => {
+            let timeline_position = ffi.call(|| event_instance.get_timeline_position())?;
+            ffi.call(|| event_instance.stop(StopMode::Immediate))?;
+            Ok(Culled::Stopped { timeline_position })
+        }
+    }
+}
+
+fn restore(ffi: &FfiScope, event_instance: EventInstance, culled: Culled) -> Result<(), Error> {
+    match culled {
+        Culled::Idle => Ok(()),
+        Culled::Paused => ffi.call(|| event_instance.set_paused(false)),
+        Culled::Stopped { timeline_position } => {
+            // Sources that were started again in the meantime are left alone
+            let state = ffi.call(|| event_instance.get_playback_state())?;
+            if !matches!(state, PlaybackState::Stopped) {
+                return Ok(());
+            }
+
+            ffi.call(|| event_instance.start())?;
+            ffi.call(|| event_instance.set_timeline_position(timeline_position))
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn cull_by_distance(
+    settings: Res<DistanceCullingSettings>,
+    spatial_settings: Res<SpatialSettings>,
+    listeners: Query<&GlobalTransform, With<AudioListener>>,
+    mut sources: Query<
+        (
+            Entity,
+            Ref<AudioSource>,
+            &GlobalTransform,
+            Option<&EmitterPosition>,
+            &mut DistanceCulling,
+        ),
+        (Without<NeverCull>, Without<InvalidAudioSource>),
+    >,
+    ffi_calls: Res<FmodFfiCalls>,
+    mut errors: FmodErrorReporter,
+) {
+    if listeners.is_empty() {
+        return;
+    }
+
+    let ffi = FfiScope::new(
+        info_span!("fmod::cull_by_distance", ffi_calls = Empty),
+        &ffi_calls,
+    );
+
+    for (entity, audio_source, transform, emitter, mut culling) in &mut sources {
+        let event_instance = audio_source.event_instance;
+
+        // The instance was replaced, so the cached max distance and culling state don't apply
+        if audio_source.is_changed() && *culling != DistanceCulling::default() {
+            *culling = DistanceCulling::default();
+        }
+
+        let max_distance = match culling.max_distance {
+            Some(max_distance) => max_distance,
+            None => match max_distance(&ffi, event_instance) {
+                Ok(max_distance) => *culling.max_distance.insert(max_distance),
+                Err(e) => {
+                    errors.report(FmodError::new(e).with_entity(entity));
+                    *culling.max_distance.insert(f32::INFINITY)
+                }
+            },
+        };
+
+        if max_distance.is_infinite() {
+            continue;
+        }
+
+        // Distances are compared in FMOD space, where the max distance is defined
+        let source = emitter.map_or(transform.translation(), |emitter| emitter.0);
+        let distance = listeners
+            .iter()
+            .map(|listener| {
+                spatial_settings
+                    .map_vector(source - listener.translation())
+                    .length()
+            })
+            .fold(f32::INFINITY, f32::min);
+
+        match culling.culled {
+            None if distance > max_distance * (1.0 + settings.hysteresis) => {
+                match cull(&ffi, event_instance, settings.mode) {
+                    Ok(culled) => culling.culled = Some(culled),
+                    Err(e) => errors.report(FmodError::new(e).with_entity(entity)),
+                }
+            }
+            Some(culled) if distance <= max_distance => {
+                if let Err(e) = restore(&ffi, event_instance, culled) {
+                    errors.report(FmodError::new(e).with_entity(entity));
+                }
+                culling.culled = None;
+            }
+            _ => {}
+        }
+    }
+}
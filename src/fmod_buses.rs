@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::prelude::{Res, ResMut, Resource};
+use libfmod::{Bus, Error, StopMode, Studio};
+
+use crate::fmod_error::FmodErrorReporter;
+use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+
+/// A resource that resolves and caches FMOD [Bus]es by path and applies changes queued from any
+/// system.
+///
+/// Changes are applied once per frame in [`PostUpdate`](bevy::app::PostUpdate), right before
+/// FMOD Studio is updated.
+///
+/// ```
+/// # use bevy::prelude::ResMut;
+/// # use bevy_fmod::prelude::FmodBuses;
+/// fn open_pause_menu(mut buses: ResMut<FmodBuses>) {
+///     buses.set_paused("bus:/SFX", true);
+///     buses.set_volume("bus:/Music", 0.5);
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct FmodBuses {
+    buses: HashMap<String, Bus>,
+    commands: Vec<(String, BusCommand)>,
+}
+
+enum BusCommand {
+    Volume(f32),
+    Mute(bool),
+    Paused(bool),
+    StopAllEvents(StopMode),
+}
+
+impl FmodBuses {
+    /// Returns the [Bus] for the given path, resolving it through FMOD Studio on first access.
+    pub fn get(&mut self, studio: &Studio, path: &str) -> Result<Bus, Error> {
+        if let Some(bus) = self.buses.get(path) {
+            return Ok(*bus);
+        }
+
+        let bus = studio.get_bus(path)?;
+        self.buses.insert(path.to_string(), bus);
+        Ok(bus)
+    }
+
+    /// Queues a volume change for the bus at the given path.
+    pub fn set_volume(&mut self, path: impl Into<String>, volume: f32) {
+        self.commands
+            .push((path.into(), BusCommand::Volume(volume)));
+    }
+
+    /// Queues muting or unmuting the bus at the given path.
+    pub fn set_mute(&mut self, path: impl Into<String>, mute: bool) {
+        self.commands.push((path.into(), BusCommand::Mute(mute)));
+    }
+
+    /// Queues pausing or resuming the bus at the given path.
+    pub fn set_paused(&mut self, path: impl Into<String>, paused: bool) {
+        self.commands
+            .push((path.into(), BusCommand::Paused(paused)));
+    }
+
+    /// Queues stopping all events routed into the bus at the given path.
+    pub fn stop_all_events(&mut self, path: impl Into<String>, stop_mode: StopMode) {
+        self.commands
+            .push((path.into(), BusCommand::StopAllEvents(stop_mode)));
+    }
+
+    /// Forgets all cached buses, e.g. after unloading the banks they belong to.
+    pub fn clear_cache(&mut self) {
+        self.buses.clear();
+    }
+
+    pub(crate) fn apply_commands(
+        mut buses: ResMut<FmodBuses>,
+        studio: Res<FmodStudio>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let commands = std::mem::take(&mut buses.commands);
+        if commands.is_empty() {
+            return;
+        }
+
+        let ffi = FfiScope::new(
+            info_span!("fmod::apply_bus_commands", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        for (path, command) in commands {
+            // Only resolving a bus that isn't cached yet calls into FMOD
+            let bus = match buses.buses.get(&path) {
+                Some(bus) => Ok(*bus),
+                None => ffi.call(|| buses.get(&studio, &path)),
+            };
+
+            let result = bus.and_then(|bus| {
+                ffi.call(|| match command {
+                    BusCommand::Volume(volume) => bus.set_volume(volume),
+                    BusCommand::Mute(mute) => bus.set_mute(mute),
+                    BusCommand::Paused(paused) => bus.set_paused(paused),
+                    BusCommand::StopAllEvents(stop_mode) => bus.stop_all_events(stop_mode),
+                })
+            });
+
+            if let Err(e) = result {
+                errors.report(e);
+            }
+        }
+    }
+}
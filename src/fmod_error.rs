@@ -0,0 +1,155 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+
+use bevy::ecs::error::{ErrorContext, ErrorHandler, error};
+use bevy::ecs::system::{SystemChangeTick, SystemName, SystemParam};
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::{Entity, Message, MessageWriter, Res, Resource};
+use libfmod::Error;
+
+/// An error returned by FMOD in a system or hook of this crate, together with the entity it
+/// occurred on.
+///
+/// Every error is sent as an [FmodErrorMessage] and passed to the [FmodErrorHandler], which logs
+/// it by default. Failures that don't come from FMOD, like meshes that can't be turned into
+/// occluder geometry or audio settings files that can't be read, are only logged.
+#[derive(Debug)]
+pub struct FmodError {
+    /// The entity the error occurred on, if any.
+    pub entity: Option<Entity>,
+    /// The error returned by FMOD.
+    pub error: Error,
+}
+
+impl FmodError {
+    /// Creates an error that didn't occur on a specific entity.
+    pub fn new(error: Error) -> Self {
+        FmodError {
+            entity: None,
+            error,
+        }
+    }
+
+    /// Sets the entity the error occurred on.
+    #[must_use]
+    pub fn with_entity(mut self, entity: Entity) -> Self {
+        self.entity = Some(entity);
+        self
+    }
+
+    /// The `FMOD_RESULT` code of the error, if it was returned by an FMOD function.
+    pub fn code(&self) -> Option<i32> {
+        match &self.error {
+            Error::Fmod { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Creates the [FmodErrorMessage] of this error.
+    pub fn message(&self) -> FmodErrorMessage {
+        FmodErrorMessage {
+            entity: self.entity,
+            function: match &self.error {
+                Error::Fmod { function, .. } => Some(function.clone()),
+                _ => None,
+            },
+            code: self.code(),
+            message: self.error.to_string(),
+        }
+    }
+}
+
+impl Display for FmodError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.entity {
+            Some(entity) => write!(f, "FMOD error on {}: {}", entity, self.error),
+            None => write!(f, "FMOD error: {}", self.error),
+        }
+    }
+}
+
+impl StdError for FmodError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<Error> for FmodError {
+    fn from(error: Error) -> Self {
+        FmodError::new(error)
+    }
+}
+
+/// Sent for every [FmodError] that occurs in the systems and hooks of this crate.
+///
+/// Use it to react to errors in-game, e.g. to despawn entities whose event instance became
+/// invalid, without having to parse logs.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct FmodErrorMessage {
+    /// The entity the error occurred on, if any.
+    pub entity: Option<Entity>,
+    /// The FMOD function that failed, if the error was returned by FMOD.
+    pub function: Option<String>,
+    /// The `FMOD_RESULT` code of the error, if the error was returned by FMOD.
+    pub code: Option<i32>,
+    /// A description of the error.
+    pub message: String,
+}
+
+/// The handler that the [FmodError]s of this crate's systems and hooks are passed to, after
+/// their [FmodErrorMessage] was sent.
+///
+/// Defaults to [`bevy::ecs::error::error`], which logs them. The errors don't go through the
+/// app's default error handler, so an FMOD error never panics unless you ask for it:
+///
+/// ```
+/// # use bevy::ecs::error::panic;
+/// # use bevy_fmod::prelude::FmodErrorHandler;
+/// let handler = FmodErrorHandler(panic);
+/// ```
+#[derive(Resource, Clone, Copy)]
+pub struct FmodErrorHandler(pub ErrorHandler);
+
+impl Default for FmodErrorHandler {
+    fn default() -> Self {
+        FmodErrorHandler(error)
+    }
+}
+
+/// Reports the errors of a system, so one failing entity doesn't keep the others from being
+/// updated.
+#[derive(SystemParam)]
+pub(crate) struct FmodErrorReporter<'w> {
+    messages: MessageWriter<'w, FmodErrorMessage>,
+    handler: Res<'w, FmodErrorHandler>,
+    system: SystemName,
+    ticks: SystemChangeTick,
+}
+
+impl FmodErrorReporter<'_> {
+    /// Sends the message of the error and passes it to the [FmodErrorHandler].
+    pub(crate) fn report(&mut self, error: impl Into<FmodError>) {
+        let error = error.into();
+        self.messages.write(error.message());
+
+        (self.handler.0)(
+            error.into(),
+            ErrorContext::System {
+                name: self.system.name(),
+                last_run: self.ticks.last_run(),
+            },
+        );
+    }
+}
+
+/// Sends the message of an error that occurred in a component hook and passes the error to the
+/// [FmodErrorHandler].
+pub(crate) fn handle_hook_error(world: &mut DeferredWorld, hook: &'static str, error: FmodError) {
+    world.write_message(error.message());
+
+    let handler = world
+        .get_resource::<FmodErrorHandler>()
+        .copied()
+        .unwrap_or_default();
+    (handler.0)(error.into(), ErrorContext::Command { name: hook.into() });
+}
@@ -1,11 +1,29 @@
 use bevy::app::PreStartup;
-use bevy::log::error;
-use bevy::prelude::{App, Plugin, PostUpdate, Res, Update, World};
+#[cfg(feature = "geometry")]
+use bevy::asset::AssetEventSystems;
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::prelude::{
+    App, IntoScheduleConfigs, Plugin, PostUpdate, Res, SystemSet, TransformSystems, Update, World,
+};
 
+use crate::command_capture::FmodCommandCapture;
+use crate::components::audio_area::AudioArea;
 use crate::components::audio_listener::AudioListener;
+#[cfg(feature = "geometry")]
+use crate::components::audio_occluder::AudioOccluder;
 use crate::components::audio_source::AudioSource;
+use crate::components::reverb_zone::ReverbZone;
+use crate::components::snapshot::Snapshot;
 use crate::components::velocity::VelocityPlugin;
+use crate::debug_log::FmodDebugLog;
+use crate::fmod_buses::FmodBuses;
+use crate::fmod_error::{
+    FmodError, FmodErrorHandler, FmodErrorMessage, FmodErrorReporter, handle_hook_error,
+};
 use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::spatial_settings::SpatialSettings;
 
 /// Initializes the FMOD Studio API and provides systems to update the audio sources and listeners.
 pub struct FmodPlugin {
@@ -15,29 +33,117 @@ pub struct FmodPlugin {
     /// Optionally you can provide paths to FMOD plugins which will then be loaded automatically.
     /// For more information see: <https://www.fmod.com/docs/2.01/api/core-guide.html#dynamic>
     pub plugin_paths: Option<&'static [&'static str]>,
+
+    /// Optionally forwards the debug output of FMOD into `bevy::log`. See [FmodDebugLog].
+    pub debug_log: Option<FmodDebugLog>,
+}
+
+/// System sets of the [FmodPlugin], which run in this order in [PostUpdate], after
+/// [TransformSystems::Propagate].
+///
+/// Use them to order your own audio systems, e.g. run a system
+/// `.after(TransformSystems::Propagate).before(FmodSystems::Spatial)` to adjust positions right
+/// before they are sent to FMOD, or `.before(FmodSystems::Update)` to make sure your FMOD calls
+/// are processed in the same frame.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FmodSystems {
+    /// Updates the [`Velocity`](crate::components::Velocity) of entities from their current
+    /// [`GlobalTransform`](bevy::prelude::GlobalTransform).
+    Velocity,
+    /// Sends the 3D attributes of [AudioSource]s and [AudioListener]s to FMOD.
+    Spatial,
+    /// Applies queued changes, like those of [FmodBuses], and ticks FMOD Studio.
+    Update,
 }
 
 impl Plugin for FmodPlugin {
     fn build(&self, app: &mut App) {
+        if let Some(debug_log) = self.debug_log {
+            debug_log.initialize();
+        }
+
+        // Bank loading is counted in the first frame
+        let ffi_calls = FmodFfiCalls::default();
+        let studio = FmodStudio::new(self.audio_banks_paths, self.plugin_paths, &ffi_calls);
+
         app.add_plugins(VelocityPlugin)
-            .insert_resource(FmodStudio::new(self.audio_banks_paths, self.plugin_paths))
+            .insert_resource(studio)
+            .insert_resource(ffi_calls)
+            .init_resource::<FmodBuses>()
+            .init_resource::<FmodCommandCapture>()
+            .init_resource::<FmodErrorHandler>()
+            .init_resource::<SpatialSettings>()
+            .add_message::<FmodErrorMessage>()
+            .configure_sets(
+                PostUpdate,
+                (
+                    FmodSystems::Velocity,
+                    FmodSystems::Spatial,
+                    FmodSystems::Update,
+                )
+                    .chain()
+                    .after(TransformSystems::Propagate),
+            )
             .add_systems(PreStartup, register_component_hooks)
+            .add_systems(Update, Snapshot::update_intensity)
+            .add_systems(
+                PostUpdate,
+                (
+                    AudioSource::remember_events,
+                    AudioSource::validate,
+                    AudioSource::recreate,
+                )
+                    .chain()
+                    .before(FmodSystems::Velocity),
+            )
             .add_systems(
-                Update,
+                PostUpdate,
                 (
                     AudioSource::update_3d_attributes,
                     AudioListener::update_3d_attributes,
-                ),
+                    AudioArea::update_3d_attributes,
+                    ReverbZone::update_reverbs,
+                )
+                    .in_set(FmodSystems::Spatial),
             )
-            .add_systems(PostUpdate, Self::update);
+            .add_systems(
+                PostUpdate,
+                (
+                    FmodBuses::apply_commands,
+                    FmodCommandCapture::apply,
+                    SpatialSettings::apply,
+                    Self::update,
+                    FmodFfiCalls::end_frame,
+                )
+                    .chain()
+                    .in_set(FmodSystems::Update),
+            );
+
+        #[cfg(feature = "geometry")]
+        app.add_systems(
+            PostUpdate,
+            (
+                AudioOccluder::update_transforms,
+                AudioOccluder::update_geometry,
+            )
+                .chain()
+                .after(AssetEventSystems)
+                .in_set(FmodSystems::Spatial),
+        );
     }
 }
 
 impl FmodPlugin {
-    fn update(studio: Res<FmodStudio>) {
-        studio
-            .update()
-            .unwrap_or_else(|e| error!("Failed to tick FMOD Studio: {}", e));
+    fn update(
+        studio: Res<FmodStudio>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        let ffi = FfiScope::new(info_span!("fmod::update", ffi_calls = Empty), &ffi_calls);
+
+        if let Err(e) = ffi.call(|| studio.update()) {
+            errors.report(e);
+        }
     }
 
     #[must_use]
@@ -45,20 +151,98 @@ impl FmodPlugin {
         FmodPlugin {
             audio_banks_paths,
             plugin_paths: None,
+            debug_log: None,
         }
     }
+
+    /// Forwards the debug output of FMOD into `bevy::log`.
+    #[must_use]
+    pub fn with_debug_log(mut self, debug_log: FmodDebugLog) -> Self {
+        self.debug_log = Some(debug_log);
+        self
+    }
 }
 
 fn register_component_hooks(world: &mut World) {
+    #[cfg(feature = "geometry")]
+    world
+        .register_component_hooks::<AudioOccluder>()
+        .on_remove(|mut world, hook_context| {
+            let entity = hook_context.entity;
+            let Some(occluder) = world.get::<AudioOccluder>(entity) else {
+                return;
+            };
+
+            if let Err(e) = occluder.release() {
+                let error = FmodError::new(e).with_entity(entity);
+                handle_hook_error(&mut world, "AudioOccluder::on_remove", error);
+            }
+        });
+
     world
         .register_component_hooks::<AudioSource>()
         .on_remove(|mut world, hook_context| {
             let entity = hook_context.entity;
-            let mut entity_mut = world.entity_mut(entity);
-            let audio_source = entity_mut.get_mut::<AudioSource>().unwrap();
+            let Some(audio_source) = world.get::<AudioSource>(entity) else {
+                return;
+            };
             let event_instance = audio_source.event_instance;
 
-            event_instance.stop(audio_source.despawn_stop_mode).unwrap();
-            event_instance.release().unwrap();
+            // The instance is already gone, e.g. because its bank was unloaded or it was
+            // released elsewhere, even if the source wasn't marked invalid yet
+            if !event_instance.is_valid() {
+                return;
+            }
+
+            if let Err(e) = event_instance
+                .stop(audio_source.despawn_stop_mode)
+                .and_then(|()| event_instance.release())
+            {
+                let error = FmodError::new(e).with_entity(entity);
+                handle_hook_error(&mut world, "AudioSource::on_remove", error);
+            }
+        });
+
+    world
+        .register_component_hooks::<ReverbZone>()
+        .on_remove(|mut world, hook_context| {
+            let entity = hook_context.entity;
+            let Some(zone) = world.get::<ReverbZone>(entity) else {
+                return;
+            };
+
+            if let Err(e) = zone.release() {
+                let error = FmodError::new(e).with_entity(entity);
+                handle_hook_error(&mut world, "ReverbZone::on_remove", error);
+            }
+        });
+
+    world
+        .register_component_hooks::<Snapshot>()
+        .on_add(|mut world, hook_context| {
+            let entity = hook_context.entity;
+            let Some(snapshot) = world.get::<Snapshot>(entity) else {
+                return;
+            };
+
+            if let Err(e) = snapshot.start() {
+                let error = FmodError::new(e).with_entity(entity);
+                handle_hook_error(&mut world, "Snapshot::on_add", error);
+            }
+        })
+        .on_remove(|mut world, hook_context| {
+            let entity = hook_context.entity;
+            let Some(snapshot) = world.get::<Snapshot>(entity) else {
+                return;
+            };
+            let event_instance = snapshot.event_instance;
+
+            if let Err(e) = event_instance
+                .stop(snapshot.stop_mode)
+                .and_then(|_| event_instance.release())
+            {
+                let error = FmodError::new(e).with_entity(entity);
+                handle_hook_error(&mut world, "Snapshot::on_remove", error);
+            }
         });
 }
@@ -1,6 +1,8 @@
 use std::fs::canonicalize;
 use std::path::Path;
 
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
 use bevy::prelude::{Deref, DerefMut, Resource, debug};
 #[cfg(feature = "live-update")]
 use libfmod::ffi::FMOD_STUDIO_INIT_LIVEUPDATE;
@@ -9,6 +11,8 @@ use libfmod::ffi::{
 };
 use libfmod::{Studio, System};
 
+use crate::profiling::{FfiScope, FmodFfiCalls};
+
 /// A resource that wraps the `Studio` object from the FMOD library.
 ///
 /// This struct provides convenient access to the FMOD Studio API within the Bevy ECS.
@@ -17,7 +21,11 @@ use libfmod::{Studio, System};
 pub struct FmodStudio(pub Studio);
 
 impl FmodStudio {
-    pub(crate) fn new(banks_paths: &[&'static str], plugin_paths: Option<&[&'static str]>) -> Self {
+    pub(crate) fn new(
+        banks_paths: &[&'static str],
+        plugin_paths: Option<&[&'static str]>,
+        ffi_calls: &FmodFfiCalls,
+    ) -> Self {
         let studio = Self::init_studio();
         let studio_core = studio.get_core_system().unwrap();
 
@@ -31,13 +39,23 @@ impl FmodStudio {
             });
         }
 
+        let ffi = FfiScope::new(
+            info_span!(
+                "fmod::load_banks",
+                banks = banks_paths.len(),
+                ffi_calls = Empty
+            ),
+            ffi_calls,
+        );
+
         banks_paths.iter().for_each(|bank_path| {
             let path = canonicalize(Path::new(bank_path))
                 .expect("Failed to canonicalize provided audio banks directory path.");
 
             debug!("Loading audio banks from: {:?}", path);
-            Self::load_bank(&studio, path.as_path());
+            ffi.call(|| Self::load_bank(&studio, path.as_path()));
         });
+        drop(ffi);
 
         FmodStudio(studio)
     }
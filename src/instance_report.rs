@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::log::info;
+use bevy::prelude::{
+    Entity, IntoScheduleConfigs, Local, Query, Real, Res, ResMut, Resource, Time, Without,
+};
+use libfmod::{Error, EventDescription, EventInstance, Guid};
+
+use crate::components::audio_source::{AudioSource, InvalidAudioSource};
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::fmod_plugin::FmodSystems;
+use crate::fmod_studio::FmodStudio;
+
+/// Settings of the [InstanceReportPlugin]. Changes take effect in the next frame.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct InstanceReportSettings {
+    /// How often the [InstanceReport] is refreshed. Walking all loaded events is too expensive to
+    /// do every frame.
+    pub interval: Duration,
+    /// Whether to log the report at the info level every time it is refreshed.
+    pub log: bool,
+}
+
+impl Default for InstanceReportSettings {
+    fn default() -> Self {
+        InstanceReportSettings {
+            interval: Duration::from_secs(1),
+            log: false,
+        }
+    }
+}
+
+/// Instances of a single loaded event, as reported by the [InstanceReport].
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventInstances {
+    /// GUID of the event.
+    pub id: Guid,
+    /// Path of the event, e.g. `event:/Ambience/Wind`. Only known if the strings bank is loaded.
+    pub path: Option<String>,
+    /// Number of instances of the event, including stopped ones that haven't been released.
+    pub instances: usize,
+    /// Number of instances that are virtualized by FMOD and not audible.
+    pub virtual_instances: usize,
+    /// Entities with an [AudioSource] playing the event.
+    pub entities: Vec<Entity>,
+}
+
+impl EventInstances {
+    /// Number of instances that are not virtualized.
+    pub fn real_instances(&self) -> usize {
+        self.instances - self.virtual_instances
+    }
+}
+
+/// Instance counts of all loaded events, refreshed by the [InstanceReportPlugin].
+///
+/// Events are sorted by their number of instances, most first, so the events responsible for
+/// hitting the channel limit are at the top.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct InstanceReport {
+    /// Instances of every loaded event.
+    pub events: Vec<EventInstances>,
+}
+
+impl InstanceReport {
+    /// Returns the instances of the event with the given path, if it is loaded. Requires the
+    /// strings bank, see [InstanceReport::get_by_id] otherwise.
+    pub fn get(&self, path: &str) -> Option<&EventInstances> {
+        self.events
+            .iter()
+            .find(|event| event.path.as_deref() == Some(path))
+    }
+
+    /// Returns the instances of the event with the given GUID, if it is loaded.
+    pub fn get_by_id(&self, id: Guid) -> Option<&EventInstances> {
+        self.events.iter().find(|event| event.id == id)
+    }
+
+    /// Total number of instances of all events.
+    pub fn instances(&self) -> usize {
+        self.events.iter().map(|event| event.instances).sum()
+    }
+
+    /// Total number of virtualized instances of all events.
+    pub fn virtual_instances(&self) -> usize {
+        self.events
+            .iter()
+            .map(|event| event.virtual_instances)
+            .sum()
+    }
+}
+
+/// Keeps an [InstanceReport] of how many instances of each loaded event exist, how many of them
+/// are virtual, and which entities they belong to, and optionally logs it.
+///
+/// Helps sound designers tune the max instances and stealing settings of events.
+pub struct InstanceReportPlugin {
+    settings: InstanceReportSettings,
+}
+
+impl InstanceReportPlugin {
+    /// Creates the plugin with the given settings.
+    #[must_use]
+    pub fn new(settings: InstanceReportSettings) -> Self {
+        InstanceReportPlugin { settings }
+    }
+}
+
+impl Default for InstanceReportPlugin {
+    fn default() -> Self {
+        Self::new(InstanceReportSettings::default())
+    }
+}
+
+impl Plugin for InstanceReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.settings.clone())
+            .init_resource::<InstanceReport>()
+            .add_systems(PostUpdate, update_report.after(FmodSystems::Update));
+    }
+}
+
+fn update_report(
+    mut report: ResMut<InstanceReport>,
+    mut last_update: Local<Option<Duration>>,
+    settings: Res<InstanceReportSettings>,
+    studio: Res<FmodStudio>,
+    sources: Query<(Entity, &AudioSource), Without<InvalidAudioSource>>,
+    time: Res<Time<Real>>,
+    mut errors: FmodErrorReporter,
+) {
+    if last_update.is_some_and(|last_update| time.elapsed() - last_update < settings.interval) {
+        return;
+    }
+    *last_update = Some(time.elapsed());
+
+    // Events are identified by GUID, as their paths are only known with the strings bank
+    let mut entities: HashMap<GuidKey, Vec<Entity>> = HashMap::new();
+
+    for (entity, audio_source) in &sources {
+        match audio_source
+            .get_description()
+            .and_then(|description| description.get_id())
+        {
+            Ok(id) => entities.entry(guid_key(id)).or_default().push(entity),
+            Err(e) => errors.report(FmodError::new(e).with_entity(entity)),
+        }
+    }
+
+    let descriptions = match loaded_events(&studio) {
+        Ok(descriptions) => descriptions,
+        Err(e) => {
+            errors.report(e);
+            return;
+        }
+    };
+
+    report.events.clear();
+
+    for description in descriptions {
+        let id = match description.get_id() {
+            Ok(id) => id,
+            Err(e) => {
+                errors.report(e);
+                continue;
+            }
+        };
+        let path = description.get_path().ok();
+
+        match description
+            .get_instance_count()
+            .and_then(|count| description.get_instance_list(count))
+        {
+            Ok(instances) => report.events.push(EventInstances {
+                virtual_instances: count_virtual(&instances),
+                instances: instances.len(),
+                entities: entities.remove(&guid_key(id)).unwrap_or_default(),
+                id,
+                path,
+            }),
+            Err(e) => errors.report(e),
+        }
+    }
+
+    report
+        .events
+        .sort_by(|a, b| b.instances.cmp(&a.instances).then(a.path.cmp(&b.path)));
+
+    if settings.log {
+        log_report(&report);
+    }
+}
+
+/// [Guid] doesn't implement `Hash`, so its fields are used as the key.
+type GuidKey = (u32, u16, u16, [u8; 8]);
+
+fn guid_key(id: Guid) -> GuidKey {
+    (id.data_1, id.data_2, id.data_3, id.data_4)
+}
+
+/// The path of the event, or its GUID in the form of `GUIDs.txt` without the strings bank.
+fn event_name(id: Guid, path: &Option<String>) -> String {
+    path.clone().unwrap_or_else(|| {
+        let [a, b, c, d, e, f, g, h] = id.data_4;
+        format!(
+            "{{{:08x}-{:04x}-{:04x}-{a:02x}{b:02x}-{c:02x}{d:02x}{e:02x}{f:02x}{g:02x}{h:02x}}}",
+            id.data_1, id.data_2, id.data_3
+        )
+    })
+}
+
+fn loaded_events(studio: &FmodStudio) -> Result<Vec<EventDescription>, Error> {
+    let mut descriptions = Vec::new();
+
+    for bank in studio.get_bank_list(studio.get_bank_count()?)? {
+        descriptions.extend(bank.get_event_list(bank.get_event_count()?)?);
+    }
+
+    Ok(descriptions)
+}
+
+fn count_virtual(instances: &[EventInstance]) -> usize {
+    // Instances that can't be queried, e.g. because they were just released, count as real
+    instances
+        .iter()
+        .filter(|instance| instance.is_virtual().unwrap_or(false))
+        .count()
+}
+
+fn log_report(report: &InstanceReport) {
+    info!(
+        "FMOD instances: {} ({} virtual)",
+        report.instances(),
+        report.virtual_instances()
+    );
+
+    for event in report.events.iter().filter(|event| event.instances > 0) {
+        info!(
+            "  {}: {} ({} virtual), entities: {:?}",
+            event_name(event.id, &event.path),
+            event.instances,
+            event.virtual_instances,
+            event.entities
+        );
+    }
+}
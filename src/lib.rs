@@ -2,19 +2,74 @@
 #![deny(clippy::wildcard_imports, missing_docs)]
 
 mod attributes_3d;
+pub mod codegen;
+#[doc(hidden)]
+pub mod command_capture;
 pub mod components;
+#[cfg(feature = "debug-gizmos")]
+#[doc(hidden)]
+pub mod debug_gizmos;
+#[doc(hidden)]
+pub mod debug_log;
+#[doc(hidden)]
+pub mod diagnostics;
+#[doc(hidden)]
+pub mod distance_culling;
+#[doc(hidden)]
+pub mod fmod_buses;
+#[doc(hidden)]
+pub mod fmod_error;
 #[doc(hidden)]
 pub mod fmod_plugin;
 #[doc(hidden)]
 pub mod fmod_studio;
+#[doc(hidden)]
+pub mod instance_report;
+#[doc(hidden)]
+pub mod occlusion;
 pub mod prelude;
+#[doc(hidden)]
+pub mod profiling;
+mod round_robin;
+#[doc(hidden)]
+pub mod spatial_settings;
 #[cfg(feature = "utilities")]
 pub mod utilities;
 
 #[doc(inline)]
-pub use fmod_plugin::FmodPlugin;
+pub use command_capture::{CommandReplayRunner, FmodCommandCapture};
+#[cfg(feature = "debug-gizmos")]
+#[doc(inline)]
+pub use debug_gizmos::{FmodDebugGizmosPlugin, FmodGizmos};
+#[doc(inline)]
+pub use debug_log::{FmodDebugLevel, FmodDebugLog};
+#[doc(inline)]
+pub use diagnostics::FmodDiagnosticsPlugin;
+#[doc(inline)]
+pub use distance_culling::{
+    CullingMode, DistanceCulling, DistanceCullingPlugin, DistanceCullingSettings, NeverCull,
+};
+#[doc(inline)]
+pub use fmod_buses::FmodBuses;
+#[doc(inline)]
+pub use fmod_error::{FmodError, FmodErrorHandler, FmodErrorMessage};
+#[doc(inline)]
+pub use fmod_plugin::{FmodPlugin, FmodSystems};
 #[doc(inline)]
 pub use fmod_studio::FmodStudio;
+#[doc(inline)]
+pub use instance_report::{
+    EventInstances, InstanceReport, InstanceReportPlugin, InstanceReportSettings,
+};
+#[doc(inline)]
+pub use occlusion::{
+    IgnoreOcclusion, Occlusion, OcclusionOutput, OcclusionPlugin, OcclusionProvider,
+    OcclusionSettings,
+};
+#[doc(inline)]
+pub use profiling::FmodFfiCalls;
+#[doc(inline)]
+pub use spatial_settings::{AxisMapping, SpatialMode, SpatialSettings};
 
 // Re-export libfmod for plugin authors:
 pub use libfmod;
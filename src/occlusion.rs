@@ -0,0 +1,295 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::system::{ReadOnlySystemParam, StaticSystemParam, SystemParamItem};
+use bevy::log::tracing::field::Empty;
+use bevy::log::{debug, info_span};
+use bevy::math::Vec3;
+use bevy::prelude::{
+    Component, DetectChanges, Entity, GlobalTransform, IntoScheduleConfigs, Local, Mut, Query, Ref,
+    Res, Resource, Time, With, Without,
+};
+
+use crate::components::audio_area::{AudioArea, EmitterPosition};
+use crate::components::audio_listener::AudioListener;
+use crate::components::audio_source::{AudioSource, InvalidAudioSource};
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::fmod_plugin::FmodSystems;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use crate::round_robin::round_robin;
+
+/// Line-of-sight test between an [AudioListener] and an [AudioSource], usually implemented with
+/// raycasts of a physics engine.
+///
+/// ```ignore
+/// struct PhysicsOcclusion;
+///
+/// impl OcclusionProvider for PhysicsOcclusion {
+///     type Param = SpatialQuery<'static, 'static>;
+///
+///     fn occlusion(query: &SpatialQuery, listener: Vec3, source: Vec3, entity: Entity) -> f32 {
+///         let Ok(direction) = Dir3::new(source - listener) else {
+///             return 0.0;
+///         };
+///         let filter = SpatialQueryFilter::default().with_excluded_entities([entity]);
+///         let hit = query.cast_ray(listener, direction, listener.distance(source), true, &filter);
+///         if hit.is_some() { 1.0 } else { 0.0 }
+///     }
+/// }
+/// ```
+pub trait OcclusionProvider: Send + Sync + 'static {
+    /// The system parameters needed for the test, e.g. the spatial query of a physics engine.
+    type Param: ReadOnlySystemParam;
+
+    /// Returns how much the path from `listener` to `source` is blocked, from `0.0` (clear line
+    /// of sight) to `1.0` (fully occluded). `entity` is the entity of the [AudioSource].
+    fn occlusion(
+        param: &SystemParamItem<Self::Param>,
+        listener: Vec3,
+        source: Vec3,
+        entity: Entity,
+    ) -> f32;
+}
+
+/// Where the occlusion value of an [AudioSource] is written to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OcclusionOutput {
+    /// Sets the event parameter with the given name, e.g. to drive a low-pass filter that was
+    /// set up in FMOD Studio. Events without the parameter are not occluded.
+    Parameter(&'static str),
+    /// Sets the 3D occlusion of the event's channel group. The direct path is occluded by the
+    /// occlusion value, the reverb path by the occlusion value times `reverb_factor`.
+    ChannelGroup {
+        /// How much of the occlusion applies to the reverb path, between `0.0` and `1.0`.
+        reverb_factor: f32,
+    },
+}
+
+/// Settings of the [OcclusionPlugin]. Changes take effect in the next frame.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct OcclusionSettings {
+    /// Where occlusion values are written to.
+    pub output: OcclusionOutput,
+    /// How long occlusion changes take to settle, which avoids audible jumps when a source moves
+    /// behind a wall.
+    pub smoothing: Duration,
+    /// How many sources are tested per frame. Sources are tested in a round-robin fashion, so
+    /// with many sources each one is tested only every few frames.
+    pub sources_per_frame: usize,
+}
+
+impl Default for OcclusionSettings {
+    fn default() -> Self {
+        OcclusionSettings {
+            output: OcclusionOutput::Parameter("Occlusion"),
+            smoothing: Duration::from_millis(100),
+            sources_per_frame: 32,
+        }
+    }
+}
+
+/// The smoothed occlusion of an [AudioSource], between `0.0` and `1.0`. Added to all sources by
+/// the [OcclusionPlugin].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Occlusion {
+    target: f32,
+    value: f32,
+    written: Option<f32>,
+    has_parameter: Option<bool>,
+}
+
+impl Occlusion {
+    /// The current, smoothed occlusion value.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// The occlusion value returned by the last line-of-sight test.
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+}
+
+/// Marker for [AudioSource]s that are never occluded, e.g. music and UI sounds.
+#[derive(Component, Default)]
+pub struct IgnoreOcclusion;
+
+/// Muffles sounds behind walls by testing the line of sight between the nearest [AudioListener]
+/// and every [AudioSource] with the [OcclusionProvider] `P`.
+///
+/// ```ignore
+/// app.add_plugins(OcclusionPlugin::<PhysicsOcclusion>::new(OcclusionSettings {
+///     output: OcclusionOutput::Parameter("Occlusion"),
+///     ..default()
+/// }));
+/// ```
+pub struct OcclusionPlugin<P: OcclusionProvider> {
+    settings: OcclusionSettings,
+    _provider: PhantomData<P>,
+}
+
+impl<P: OcclusionProvider> OcclusionPlugin<P> {
+    /// Creates the plugin with the given settings.
+    #[must_use]
+    pub fn new(settings: OcclusionSettings) -> Self {
+        OcclusionPlugin {
+            settings,
+            _provider: PhantomData,
+        }
+    }
+}
+
+impl<P: OcclusionProvider> Default for OcclusionPlugin<P> {
+    fn default() -> Self {
+        Self::new(OcclusionSettings::default())
+    }
+}
+
+impl<P: OcclusionProvider> Plugin for OcclusionPlugin<P> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.settings.clone())
+            .register_required_components::<AudioSource, Occlusion>()
+            .add_systems(
+                PostUpdate,
+                (test_occlusion::<P>, apply_occlusion)
+                    .chain()
+                    .after(AudioArea::update_3d_attributes)
+                    .in_set(FmodSystems::Spatial),
+            );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn test_occlusion<P: OcclusionProvider>(
+    param: StaticSystemParam<P::Param>,
+    settings: Res<OcclusionSettings>,
+    listeners: Query<&GlobalTransform, With<AudioListener>>,
+    mut sources: Query<(
+        Entity,
+        &GlobalTransform,
+        Option<&EmitterPosition>,
+        &mut Occlusion,
+        Option<&IgnoreOcclusion>,
+    )>,
+    mut next_source: Local<usize>,
+) {
+    let source_count = sources.iter().len();
+    if source_count == 0 || listeners.is_empty() {
+        return;
+    }
+
+    let tests = settings.sources_per_frame.min(source_count);
+    let _span = info_span!("fmod::test_occlusion", sources = tests).entered();
+
+    let test = |(entity, transform, emitter, mut occlusion, ignore): (
+        Entity,
+        &GlobalTransform,
+        Option<&EmitterPosition>,
+        Mut<Occlusion>,
+        Option<&IgnoreOcclusion>,
+    )| {
+        if ignore.is_some() {
+            occlusion.target = 0.0;
+            return;
+        }
+
+        let source = emitter.map_or(transform.translation(), |emitter| emitter.0);
+        let listener = listeners
+            .iter()
+            .map(GlobalTransform::translation)
+            .min_by(|a, b| {
+                a.distance_squared(source)
+                    .total_cmp(&b.distance_squared(source))
+            })
+            .unwrap_or(source);
+
+        occlusion.target = P::occlusion(&param, listener, source, entity).clamp(0.0, 1.0);
+    };
+
+    round_robin(sources.iter_mut(), &mut next_source, tests).for_each(test);
+}
+
+fn apply_occlusion(
+    settings: Res<OcclusionSettings>,
+    time: Res<Time>,
+    mut sources: Query<(Entity, Ref<AudioSource>, &mut Occlusion), Without<InvalidAudioSource>>,
+    ffi_calls: Res<FmodFfiCalls>,
+    mut errors: FmodErrorReporter,
+) {
+    let ffi = FfiScope::new(
+        info_span!("fmod::apply_occlusion", ffi_calls = Empty),
+        &ffi_calls,
+    );
+
+    let smoothing = settings.smoothing.as_secs_f32();
+    let blend = if smoothing > 0.0 {
+        1.0 - (-time.delta_secs() / smoothing).exp()
+    } else {
+        1.0
+    };
+
+    for (entity, audio_source, mut occlusion) in &mut sources {
+        // The instance or the output changed, so the value has to be written again
+        if settings.is_changed() || audio_source.is_changed() {
+            occlusion.written = None;
+            occlusion.has_parameter = None;
+        }
+
+        let value = occlusion.value + (occlusion.target - occlusion.value) * blend;
+        let value = if (occlusion.target - value).abs() < 0.001 {
+            occlusion.target
+        } else {
+            value
+        };
+
+        occlusion.value = value;
+        if occlusion.written == Some(value) {
+            continue;
+        }
+
+        let result = match settings.output {
+            OcclusionOutput::Parameter(name) => {
+                // Checked once per source, so events without the parameter don't fail every time
+                // their occlusion changes
+                let has_parameter = *occlusion.has_parameter.get_or_insert_with(|| {
+                    let has_parameter = ffi
+                        .call(|| audio_source.get_parameter_by_name(name))
+                        .is_ok();
+                    if !has_parameter {
+                        debug!(
+                            "Event of audio source {} has no {} parameter, skipping occlusion",
+                            entity, name
+                        );
+                    }
+                    has_parameter
+                });
+
+                if has_parameter {
+                    ffi.call(|| audio_source.set_parameter_by_name(name, value, false))
+                        .map(|()| true)
+                } else {
+                    Ok(true)
+                }
+            }
+            // The channel group only exists while the event is playing
+            OcclusionOutput::ChannelGroup { reverb_factor } => {
+                match ffi.call(|| audio_source.get_channel_group()) {
+                    Ok(channel_group) => ffi
+                        .call(|| channel_group.set_3d_occlusion(value, value * reverb_factor))
+                        .map(|()| true),
+                    Err(_) => Ok(false),
+                }
+            }
+        };
+
+        match result {
+            Ok(true) => occlusion.written = Some(value),
+            Ok(false) => {}
+            Err(e) => {
+                errors.report(FmodError::new(e).with_entity(entity));
+                occlusion.written = Some(value);
+            }
+        }
+    }
+}
@@ -6,11 +6,57 @@
 //! use bevy_fmod::prelude::*;
 //! ```
 
+pub use crate::command_capture::CommandReplayRunner;
+pub use crate::command_capture::FmodCommandCapture;
+pub use crate::components::audio_area::AreaShape;
+pub use crate::components::audio_area::AudioArea;
+pub use crate::components::audio_area::EmitterPosition;
 pub use crate::components::audio_listener::AudioListener;
+pub use crate::components::audio_listener::ListenerAttenuationTarget;
+pub use crate::components::audio_listener::ListenerWeight;
+#[cfg(feature = "geometry")]
+pub use crate::components::audio_occluder::AudioOccluder;
 pub use crate::components::audio_source::AudioSource;
+pub use crate::components::audio_source::InvalidAudioSource;
+pub use crate::components::audio_source::RecreateAudioSource;
 pub use crate::components::bundles::SpatialAudioBundle;
 pub use crate::components::bundles::SpatialListenerBundle;
+pub use crate::components::reverb_zone::ReverbPreset;
+pub use crate::components::reverb_zone::ReverbZone;
+pub use crate::components::snapshot::Snapshot;
 pub use crate::components::velocity::Velocity;
+pub use crate::components::velocity::VelocityFromComponent;
+#[cfg(feature = "debug-gizmos")]
+pub use crate::debug_gizmos::FmodDebugGizmosPlugin;
+#[cfg(feature = "debug-gizmos")]
+pub use crate::debug_gizmos::FmodGizmos;
+pub use crate::debug_log::FmodDebugLevel;
+pub use crate::debug_log::FmodDebugLog;
+pub use crate::diagnostics::FmodDiagnosticsPlugin;
+pub use crate::distance_culling::CullingMode;
+pub use crate::distance_culling::DistanceCulling;
+pub use crate::distance_culling::DistanceCullingPlugin;
+pub use crate::distance_culling::DistanceCullingSettings;
+pub use crate::distance_culling::NeverCull;
+pub use crate::fmod_buses::FmodBuses;
+pub use crate::fmod_error::FmodError;
+pub use crate::fmod_error::FmodErrorHandler;
+pub use crate::fmod_error::FmodErrorMessage;
 pub use crate::fmod_plugin::FmodPlugin;
+pub use crate::fmod_plugin::FmodSystems;
 pub use crate::fmod_studio::FmodStudio;
+pub use crate::instance_report::EventInstances;
+pub use crate::instance_report::InstanceReport;
+pub use crate::instance_report::InstanceReportPlugin;
+pub use crate::instance_report::InstanceReportSettings;
+pub use crate::occlusion::IgnoreOcclusion;
+pub use crate::occlusion::Occlusion;
+pub use crate::occlusion::OcclusionOutput;
+pub use crate::occlusion::OcclusionPlugin;
+pub use crate::occlusion::OcclusionProvider;
+pub use crate::occlusion::OcclusionSettings;
+pub use crate::profiling::FmodFfiCalls;
+pub use crate::spatial_settings::AxisMapping;
+pub use crate::spatial_settings::SpatialMode;
+pub use crate::spatial_settings::SpatialSettings;
 pub use libfmod::StopMode;
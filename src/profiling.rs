@@ -0,0 +1,74 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy::log::tracing::Span;
+use bevy::log::tracing::span::EnteredSpan;
+use bevy::prelude::{ResMut, Resource};
+
+/// A resource with the number of calls into FMOD made per frame by the systems of this crate, to
+/// see how much the integration costs. Calls are counted where they are made, separately for
+/// every app, including those of the utilities and the debug gizmos.
+///
+/// Not counted are the calls of the [FmodDiagnosticsPlugin](crate::FmodDiagnosticsPlugin), the
+/// [InstanceReport](crate::InstanceReport), the `SnapshotOnState` utility and component hooks,
+/// as well as calls made through the [FmodStudio](crate::FmodStudio) or an
+/// [AudioSource](crate::components::AudioSource) by your own systems.
+///
+/// The systems, and bank loading, are wrapped in `tracing` spans with an `ffi_calls` field, so
+/// the counts also show up in Tracy or Chrome traces when the `bevy/trace_tracy` or
+/// `bevy/trace_chrome` feature is enabled.
+#[derive(Resource, Debug, Default)]
+pub struct FmodFfiCalls {
+    /// Number of FFI calls made in the last frame.
+    pub last_frame: usize,
+    current: AtomicUsize,
+}
+
+impl FmodFfiCalls {
+    /// Number of FFI calls made in the current frame so far.
+    pub fn current_frame(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn end_frame(mut ffi_calls: ResMut<FmodFfiCalls>) {
+        ffi_calls.last_frame = std::mem::take(ffi_calls.current.get_mut());
+    }
+}
+
+/// Counts the FFI calls made through it. When dropped, the count is recorded in the `ffi_calls`
+/// field of its span and added to the [FmodFfiCalls] of the frame.
+pub(crate) struct FfiScope<'a> {
+    span: EnteredSpan,
+    calls: Cell<usize>,
+    ffi_calls: &'a FmodFfiCalls,
+}
+
+impl<'a> FfiScope<'a> {
+    /// Enters the span, which needs an `ffi_calls` field.
+    pub(crate) fn new(span: Span, ffi_calls: &'a FmodFfiCalls) -> Self {
+        FfiScope {
+            span: span.entered(),
+            calls: Cell::new(0),
+            ffi_calls,
+        }
+    }
+
+    /// Makes a call into FMOD and counts it.
+    pub(crate) fn call<T>(&self, call: impl FnOnce() -> T) -> T {
+        self.calls.set(self.calls.get() + 1);
+        call()
+    }
+
+    /// The entered span, e.g. to record further fields.
+    pub(crate) fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
+impl Drop for FfiScope<'_> {
+    fn drop(&mut self) {
+        let calls = self.calls.get();
+        self.span.record("ffi_calls", calls);
+        self.ffi_calls.current.fetch_add(calls, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,20 @@
+/// Returns up to `max` items of `iter`, starting at the `next` item and continuing after the last
+/// returned item on the next call, wrapping around to the first ones. This spreads work like
+/// validation or raycasts over several frames without starving any item.
+pub(crate) fn round_robin<I: ExactSizeIterator>(
+    iter: I,
+    next: &mut usize,
+    max: usize,
+) -> impl Iterator<Item = I::Item> {
+    let len = iter.len();
+    let count = max.min(len);
+    let start = next.checked_rem(len).unwrap_or(0);
+    *next = (start + count).checked_rem(len).unwrap_or(0);
+
+    // Items past the end are taken from the start
+    let wrapped = (start + count).saturating_sub(len);
+    iter.enumerate()
+        .take(start + count)
+        .filter(move |(index, _)| *index >= start || *index < wrapped)
+        .map(|(_, item)| item)
+}
@@ -0,0 +1,168 @@
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::math::{Mat3, Vec3};
+use bevy::prelude::{DetectChanges, Res, Resource};
+
+use crate::fmod_error::FmodErrorReporter;
+use crate::fmod_studio::FmodStudio;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+
+/// Global 3D settings of FMOD and the mapping from Bevy world space into FMOD space.
+///
+/// Insert this resource to change the defaults. Changes are applied to FMOD through
+/// `System::set_3d_settings` at the end of the frame.
+///
+/// ```
+/// # use bevy::prelude::App;
+/// # use bevy_fmod::prelude::{AxisMapping, SpatialSettings};
+/// App::new().insert_resource(SpatialSettings {
+///     doppler_scale: 0.5,
+///     axes: AxisMapping::Z_UP,
+///     ..SpatialSettings::default()
+/// });
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct SpatialSettings {
+    /// Scales the Doppler effect of all sources. `1.0` is physically accurate, `0.0` disables it.
+    pub doppler_scale: f32,
+    /// The number of FMOD units per meter, used for the Doppler effect and core API distances.
+    pub distance_factor: f32,
+    /// Scales the rolloff of sounds that use the core API's inverse or inverse tapered rolloff.
+    pub rolloff_scale: f32,
+    /// Maps vectors from Bevy world space into FMOD space. Only used in [SpatialMode::ThreeD].
+    pub axes: AxisMapping,
+    /// Whether positions are treated as 3D or projected from a 2D world.
+    pub mode: SpatialMode,
+}
+
+impl Default for SpatialSettings {
+    fn default() -> Self {
+        SpatialSettings {
+            doppler_scale: 1.0,
+            distance_factor: 1.0,
+            rolloff_scale: 1.0,
+            axes: AxisMapping::IDENTITY,
+            mode: SpatialMode::ThreeD,
+        }
+    }
+}
+
+impl SpatialSettings {
+    /// Maps a position or velocity from Bevy world space into FMOD space.
+    pub fn map_vector(&self, vector: Vec3) -> Vec3 {
+        match self.mode {
+            SpatialMode::ThreeD => self.axes.map_vector(vector),
+            SpatialMode::SideScroller { pixels_per_meter } => {
+                Vec3::new(vector.x, vector.y, 0.0) / pixels_per_meter
+            }
+            SpatialMode::TopDown { pixels_per_meter } => {
+                Vec3::new(vector.x, 0.0, -vector.y) / pixels_per_meter
+            }
+        }
+    }
+
+    /// Maps the forward and up directions of an entity into FMOD space.
+    ///
+    /// In the 2D modes, all entities share a fixed basis facing into the screen (side-scroller)
+    /// or towards the top of the screen (top-down).
+    pub fn map_orientation(&self, forward: Vec3, up: Vec3) -> (Vec3, Vec3) {
+        match self.mode {
+            SpatialMode::ThreeD => (
+                self.axes.map_direction(forward),
+                self.axes.map_direction(up),
+            ),
+            SpatialMode::SideScroller { .. } | SpatialMode::TopDown { .. } => {
+                (Vec3::NEG_Z, Vec3::Y)
+            }
+        }
+    }
+
+    pub(crate) fn apply(
+        settings: Res<SpatialSettings>,
+        studio: Res<FmodStudio>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        if !settings.is_changed() {
+            return;
+        }
+
+        let ffi = FfiScope::new(
+            info_span!("fmod::apply_3d_settings", ffi_calls = Empty),
+            &ffi_calls,
+        );
+        let result = ffi.call(|| studio.get_core_system()).and_then(|core| {
+            ffi.call(|| {
+                core.set_3d_settings(
+                    settings.doppler_scale,
+                    settings.distance_factor,
+                    settings.rolloff_scale,
+                )
+            })
+        });
+
+        if let Err(e) = result {
+            errors.report(e);
+        }
+    }
+}
+
+/// How positions in the Bevy world are interpreted.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SpatialMode {
+    /// Positions and orientations are used as they are, mapped through [AxisMapping].
+    #[default]
+    ThreeD,
+    /// A 2D world seen from the side, with X to the right and Y up. The Z coordinate is treated
+    /// as layer depth and ignored.
+    SideScroller {
+        /// How many world units make up one meter in FMOD.
+        pixels_per_meter: f32,
+    },
+    /// A 2D world seen from above, with X to the right and Y towards the top of the screen.
+    /// The top of the screen is in front of the listener. The Z coordinate is treated as layer
+    /// depth and ignored.
+    TopDown {
+        /// How many world units make up one meter in FMOD.
+        pixels_per_meter: f32,
+    },
+}
+
+/// A linear mapping from Bevy world space into FMOD space.
+///
+/// FMOD is initialized with a right-handed, Y-up coordinate system like Bevy's, so the default
+/// mapping is the identity. Projects that use a different world-up axis or unit scale can use a
+/// custom matrix. Positions and velocities are transformed by the full matrix, while forward and
+/// up vectors are re-normalized afterwards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AxisMapping(pub Mat3);
+
+impl AxisMapping {
+    /// Bevy vectors are passed to FMOD unchanged.
+    pub const IDENTITY: AxisMapping = AxisMapping(Mat3::IDENTITY);
+
+    /// For worlds where Z is up and Y is forward, e.g. assets authored in Blender.
+    pub const Z_UP: AxisMapping = AxisMapping(Mat3::from_cols(Vec3::X, Vec3::NEG_Z, Vec3::Y));
+
+    /// Scales all positions and velocities, e.g. `0.01` for worlds measured in centimeters.
+    #[must_use]
+    pub fn with_scale(self, scale: f32) -> Self {
+        AxisMapping(self.0 * scale)
+    }
+
+    /// Maps a position or velocity into FMOD space.
+    pub fn map_vector(&self, vector: Vec3) -> Vec3 {
+        self.0 * vector
+    }
+
+    /// Maps a direction into FMOD space.
+    pub fn map_direction(&self, direction: Vec3) -> Vec3 {
+        (self.0 * direction).normalize_or_zero()
+    }
+}
+
+impl Default for AxisMapping {
+    fn default() -> Self {
+        AxisMapping::IDENTITY
+    }
+}
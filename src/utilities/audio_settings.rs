@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "persistence")]
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "persistence")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "persistence")]
+use std::time::Duration;
+
+use crate::FmodStudio;
+use crate::fmod_error::FmodErrorReporter;
+use crate::fmod_plugin::FmodSystems;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+#[cfg(feature = "persistence")]
+use bevy::app::AppExit;
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+#[cfg(feature = "persistence")]
+use bevy::log::{error, warn};
+use bevy::prelude::{DetectChanges, IntoScheduleConfigs, Res, Resource};
+#[cfg(feature = "persistence")]
+use bevy::prelude::{MessageReader, Real, ResMut, Time};
+#[cfg(feature = "persistence")]
+use serde::{Deserialize, Serialize};
+
+/// User-facing volume settings, where each channel (e.g. "Music") controls an FMOD VCA.
+///
+/// Volumes are slider values between `0.0` and `1.0`. How they map to the VCA volume is
+/// controlled by [VolumeScale]. Changing the resource applies the new volumes to FMOD.
+///
+/// ```
+/// # use bevy_fmod::utilities::{AudioSettings, AudioSettingsPlugin};
+/// let settings = AudioSettings::default()
+///     .with_channel("Master", "vca:/Master", 1.0)
+///     .with_channel("Music", "vca:/Music", 0.8)
+///     .with_channel("SFX", "vca:/SFX", 1.0);
+///
+/// let plugin = AudioSettingsPlugin::new(settings);
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(Serialize, Deserialize))]
+pub struct AudioSettings {
+    /// The channels by user-facing name.
+    pub channels: BTreeMap<String, AudioChannel>,
+    /// How slider values are converted to VCA volumes.
+    pub scale: VolumeScale,
+}
+
+/// A single user-facing volume channel of the [AudioSettings].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(Serialize, Deserialize))]
+pub struct AudioChannel {
+    /// Path of the VCA this channel controls, e.g. `vca:/Music`.
+    pub vca_path: String,
+    /// Slider value between `0.0` and `1.0`.
+    pub volume: f32,
+}
+
+/// Conversion from slider values to VCA volumes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(Serialize, Deserialize))]
+pub enum VolumeScale {
+    /// The slider value is used as the linear VCA volume.
+    #[default]
+    Linear,
+    /// The slider maps linearly to decibels, from `floor` (a negative dB value) at the lowest
+    /// audible position to 0 dB at `1.0`. A slider value of `0.0` is silent.
+    ///
+    /// This matches how loudness is perceived much better than [VolumeScale::Linear].
+    Decibels {
+        /// The attenuation in dB just above a slider value of `0.0`, e.g. `-60.0`.
+        floor: f32,
+    },
+}
+
+impl VolumeScale {
+    /// Returns the linear VCA volume for the given slider value.
+    pub fn gain(&self, volume: f32) -> f32 {
+        let volume = volume.clamp(0.0, 1.0);
+
+        match self {
+            VolumeScale::Linear => volume,
+            VolumeScale::Decibels { .. } if volume <= 0.0 => 0.0,
+            VolumeScale::Decibels { floor } => 10f32.powf(floor * (1.0 - volume) / 20.0),
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Adds a channel controlling the VCA at `vca_path`.
+    #[must_use]
+    pub fn with_channel(
+        mut self,
+        name: impl Into<String>,
+        vca_path: impl Into<String>,
+        volume: f32,
+    ) -> Self {
+        self.channels.insert(
+            name.into(),
+            AudioChannel {
+                vca_path: vca_path.into(),
+                volume: volume.clamp(0.0, 1.0),
+            },
+        );
+        self
+    }
+
+    /// Sets the [VolumeScale] used for all channels.
+    #[must_use]
+    pub fn with_scale(mut self, scale: VolumeScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns the slider value of the given channel.
+    pub fn volume(&self, channel: &str) -> Option<f32> {
+        self.channels.get(channel).map(|channel| channel.volume)
+    }
+
+    /// Sets the slider value of the given channel. Unknown channels are ignored.
+    pub fn set_volume(&mut self, channel: &str, volume: f32) {
+        if let Some(channel) = self.channels.get_mut(channel) {
+            channel.volume = volume.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Reads settings from a RON file.
+    #[cfg(feature = "persistence")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AudioSettingsError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    /// Writes the settings to a RON file.
+    #[cfg(feature = "persistence")]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AudioSettingsError> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Takes the volumes of all channels that exist in both settings from `other`.
+    #[cfg(feature = "persistence")]
+    fn merge_volumes(&mut self, other: &AudioSettings) {
+        for (name, channel) in &other.channels {
+            self.set_volume(name, channel.volume);
+        }
+    }
+
+    fn apply(
+        settings: Res<AudioSettings>,
+        studio: Res<FmodStudio>,
+        ffi_calls: Res<FmodFfiCalls>,
+        mut errors: FmodErrorReporter,
+    ) {
+        if !settings.is_changed() {
+            return;
+        }
+
+        let ffi = FfiScope::new(
+            info_span!("fmod::apply_audio_settings", ffi_calls = Empty),
+            &ffi_calls,
+        );
+
+        for channel in settings.channels.values() {
+            let result = ffi
+                .call(|| studio.get_vca(&channel.vca_path))
+                .and_then(|vca| ffi.call(|| vca.set_volume(settings.scale.gain(channel.volume))));
+
+            if let Err(e) = result {
+                errors.report(e);
+            }
+        }
+    }
+}
+
+/// An error reading or writing [AudioSettings] from or to a RON file.
+#[cfg(feature = "persistence")]
+#[derive(Debug)]
+pub enum AudioSettingsError {
+    /// The file couldn't be read or written.
+    Io(std::io::Error),
+    /// The file doesn't contain valid settings.
+    Parse(ron::error::SpannedError),
+    /// The settings couldn't be serialized.
+    Serialize(ron::Error),
+}
+
+#[cfg(feature = "persistence")]
+impl Display for AudioSettingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioSettingsError::Io(e) => write!(f, "{}", e),
+            AudioSettingsError::Parse(e) => write!(f, "{}", e),
+            AudioSettingsError::Serialize(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl std::error::Error for AudioSettingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AudioSettingsError::Io(e) => Some(e),
+            AudioSettingsError::Parse(e) => Some(e),
+            AudioSettingsError::Serialize(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl From<std::io::Error> for AudioSettingsError {
+    fn from(error: std::io::Error) -> Self {
+        AudioSettingsError::Io(error)
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl From<ron::error::SpannedError> for AudioSettingsError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        AudioSettingsError::Parse(error)
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl From<ron::Error> for AudioSettingsError {
+    fn from(error: ron::Error) -> Self {
+        AudioSettingsError::Serialize(error)
+    }
+}
+
+/// Inserts the [AudioSettings] resource and applies its volumes to the FMOD VCAs whenever it
+/// changes.
+///
+/// With the `persistence` feature, the settings can be loaded from and saved to a RON file so
+/// they survive restarts.
+pub struct AudioSettingsPlugin {
+    settings: AudioSettings,
+    #[cfg(feature = "persistence")]
+    file: Option<PathBuf>,
+    #[cfg(feature = "persistence")]
+    save_delay: Duration,
+}
+
+impl AudioSettingsPlugin {
+    /// Creates the plugin with the given default settings.
+    #[must_use]
+    pub fn new(settings: AudioSettings) -> Self {
+        AudioSettingsPlugin {
+            settings,
+            #[cfg(feature = "persistence")]
+            file: None,
+            #[cfg(feature = "persistence")]
+            save_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Loads the volumes from the given RON file on startup, if it exists, and saves the settings
+    /// to it once they stopped changing, e.g. when the user lets go of a volume slider, and when
+    /// the app exits.
+    #[cfg(feature = "persistence")]
+    #[must_use]
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Sets how long the settings have to stay unchanged before they are saved. Defaults to one
+    /// second.
+    #[cfg(feature = "persistence")]
+    #[must_use]
+    pub fn with_save_delay(mut self, save_delay: Duration) -> Self {
+        self.save_delay = save_delay;
+        self
+    }
+}
+
+#[cfg(feature = "persistence")]
+#[derive(Resource)]
+struct AudioSettingsFile {
+    path: PathBuf,
+    save_delay: Duration,
+    changed_at: Option<Duration>,
+}
+
+#[cfg(feature = "persistence")]
+fn save_audio_settings(
+    settings: Res<AudioSettings>,
+    file: Option<ResMut<AudioSettingsFile>>,
+    time: Res<Time<Real>>,
+    mut exit: MessageReader<AppExit>,
+) {
+    let Some(mut file) = file else {
+        return;
+    };
+
+    if settings.is_changed() && !settings.is_added() {
+        file.changed_at = Some(time.elapsed());
+    }
+
+    let exiting = exit.read().next().is_some();
+    let Some(changed_at) = file.changed_at else {
+        return;
+    };
+
+    if !exiting && time.elapsed() - changed_at < file.save_delay {
+        return;
+    }
+
+    file.changed_at = None;
+
+    if let Err(e) = settings.save(&file.path) {
+        error!("Failed to save audio settings to {:?}: {}", file.path, e);
+    }
+}
+
+impl Plugin for AudioSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        #[allow(unused_mut)]
+        let mut settings = self.settings.clone();
+
+        #[cfg(feature = "persistence")]
+        if let Some(file) = &self.file {
+            if file.exists() {
+                match AudioSettings::load(file) {
+                    Ok(stored) => settings.merge_volumes(&stored),
+                    Err(e) => warn!("Failed to load audio settings from {:?}: {}", file, e),
+                }
+            }
+
+            app.insert_resource(AudioSettingsFile {
+                path: file.clone(),
+                save_delay: self.save_delay,
+                changed_at: None,
+            });
+        }
+
+        app.insert_resource(settings);
+
+        #[cfg(feature = "persistence")]
+        app.add_systems(PostUpdate, save_audio_settings.after(AudioSettings::apply));
+
+        app.add_systems(PostUpdate, AudioSettings::apply.before(FmodSystems::Update));
+    }
+}
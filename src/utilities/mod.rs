@@ -3,7 +3,19 @@
 //! Collection of useful plugins, components or systems that are not part of the FMOD API but help
 //! when developing bevy games with FMOD.
 
+mod audio_settings;
 mod mute_when_unfocused;
+mod pause_with_virtual_time;
+mod snapshot_on_state;
 
+#[cfg(feature = "persistence")]
+#[doc(inline)]
+pub use audio_settings::AudioSettingsError;
+#[doc(inline)]
+pub use audio_settings::{AudioChannel, AudioSettings, AudioSettingsPlugin, VolumeScale};
 #[doc(inline)]
 pub use mute_when_unfocused::MuteWhenUnfocused;
+#[doc(inline)]
+pub use pause_with_virtual_time::{IgnoreVirtualTime, PauseWithVirtualTime};
+#[doc(inline)]
+pub use snapshot_on_state::SnapshotOnState;
@@ -1,6 +1,5 @@
-use crate::FmodStudio;
+use crate::FmodBuses;
 use bevy::app::{App, Plugin, Update};
-use bevy::ecs::error::Result;
 use bevy::prelude::{Entity, Local, MessageReader, ResMut, Single, With};
 use bevy::window::{PrimaryWindow, WindowFocused};
 
@@ -18,14 +17,12 @@ fn mute_when_unfocused(
     mut focus_event: MessageReader<WindowFocused>,
     primary_window: Single<Entity, With<PrimaryWindow>>,
     mut last_focus_state: Local<bool>,
-    studio: ResMut<FmodStudio>,
-) -> Result {
+    mut buses: ResMut<FmodBuses>,
+) {
     for WindowFocused { window, focused } in focus_event.read() {
         if *window == *primary_window && *last_focus_state != *focused {
-            studio.get_bus("bus:/")?.set_mute(!focused)?;
+            buses.set_mute("bus:/", !focused);
             *last_focus_state = *focused;
         }
     }
-
-    Ok(())
 }
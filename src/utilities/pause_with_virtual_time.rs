@@ -0,0 +1,184 @@
+use crate::components::audio_source::{AudioSource, InvalidAudioSource};
+use crate::fmod_buses::FmodBuses;
+use crate::fmod_error::{FmodError, FmodErrorReporter};
+use crate::fmod_plugin::FmodSystems;
+use crate::profiling::{FfiScope, FmodFfiCalls};
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::log::info_span;
+use bevy::log::tracing::field::Empty;
+use bevy::prelude::{
+    Added, Commands, Component, Entity, Has, IntoScheduleConfigs, Local, Query, Res, ResMut,
+    Resource, Time, Virtual, With, Without,
+};
+
+/// When this plugin is added, gameplay audio is paused while [`Time<Virtual>`](Virtual) is paused
+/// and resumed when it is unpaused.
+///
+/// Depending on [PauseWithVirtualTime::bus] or [PauseWithVirtualTime::audio_sources], either a
+/// single bus (e.g. `bus:/Gameplay`) or all [AudioSource]s are paused. Route menu sounds through a
+/// different bus, or mark their sources with [IgnoreVirtualTime], to keep them playing. In the
+/// latter mode, sources that are spawned or started during the pause are paused as well.
+///
+/// With [PauseWithVirtualTime::with_pitch_scaling], the pitch of all affected [AudioSource]s
+/// follows [`Time::<Virtual>::relative_speed`](Time::relative_speed), so slow motion also slows
+/// down the audio. This overrides pitch values set on these sources manually.
+#[derive(Resource, Clone)]
+pub struct PauseWithVirtualTime {
+    bus: Option<&'static str>,
+    scale_pitch: bool,
+}
+
+/// Marker for [AudioSource]s that keep playing and keep their pitch regardless of
+/// [`Time<Virtual>`](Virtual), e.g. menu and UI sounds.
+#[derive(Component, Default)]
+pub struct IgnoreVirtualTime;
+
+/// Marks sources that were paused by [PauseWithVirtualTime], so that sources paused by the user
+/// stay paused when virtual time resumes.
+#[derive(Component)]
+struct PausedByVirtualTime;
+
+impl PauseWithVirtualTime {
+    /// Pauses the bus at the given path, e.g. `bus:/Gameplay`.
+    #[must_use]
+    pub fn bus(path: &'static str) -> Self {
+        PauseWithVirtualTime {
+            bus: Some(path),
+            scale_pitch: false,
+        }
+    }
+
+    /// Pauses all [AudioSource]s that are not marked with [IgnoreVirtualTime].
+    #[must_use]
+    pub fn audio_sources() -> Self {
+        PauseWithVirtualTime {
+            bus: None,
+            scale_pitch: false,
+        }
+    }
+
+    /// Scales the pitch of [AudioSource]s with the relative speed of virtual time.
+    #[must_use]
+    pub fn with_pitch_scaling(mut self) -> Self {
+        self.scale_pitch = true;
+        self
+    }
+}
+
+impl Plugin for PauseWithVirtualTime {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.clone()).add_systems(
+            PostUpdate,
+            (pause_with_virtual_time, scale_pitch_with_virtual_time).before(FmodSystems::Update),
+        );
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn pause_with_virtual_time(
+    mut commands: Commands,
+    settings: Res<PauseWithVirtualTime>,
+    time: Res<Time<Virtual>>,
+    mut buses: ResMut<FmodBuses>,
+    sources: Query<
+        (Entity, &AudioSource),
+        (
+            Without<IgnoreVirtualTime>,
+            Without<InvalidAudioSource>,
+            Without<PausedByVirtualTime>,
+        ),
+    >,
+    paused_sources: Query<
+        (Entity, &AudioSource, Has<InvalidAudioSource>),
+        With<PausedByVirtualTime>,
+    >,
+    mut was_paused: Local<bool>,
+    ffi_calls: Res<FmodFfiCalls>,
+    mut errors: FmodErrorReporter,
+) {
+    let ffi = FfiScope::new(
+        info_span!("fmod::pause_with_virtual_time", ffi_calls = Empty),
+        &ffi_calls,
+    );
+
+    let paused = time.is_paused();
+    let changed = paused != *was_paused;
+    *was_paused = paused;
+
+    if let Some(bus) = settings.bus {
+        if changed {
+            buses.set_paused(bus, paused);
+        }
+        return;
+    }
+
+    if paused {
+        // Checked every frame of the pause, so sources that are spawned or started during the
+        // pause don't play either. Sources paused before are skipped.
+        for (entity, source) in &sources {
+            let result = match ffi.call(|| source.get_paused()) {
+                Ok(true) => continue,
+                Ok(false) => ffi.call(|| source.set_paused(true)),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => {
+                    commands.entity(entity).insert(PausedByVirtualTime);
+                }
+                Err(e) => errors.report(FmodError::new(e).with_entity(entity)),
+            }
+        }
+    } else if changed {
+        for (entity, source, invalid) in &paused_sources {
+            if !invalid && let Err(e) = ffi.call(|| source.set_paused(false)) {
+                errors.report(FmodError::new(e).with_entity(entity));
+            }
+            commands.entity(entity).remove::<PausedByVirtualTime>();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn scale_pitch_with_virtual_time(
+    settings: Res<PauseWithVirtualTime>,
+    time: Res<Time<Virtual>>,
+    sources: Query<
+        (Entity, &AudioSource),
+        (Without<IgnoreVirtualTime>, Without<InvalidAudioSource>),
+    >,
+    added_sources: Query<
+        (Entity, &AudioSource),
+        (
+            Added<AudioSource>,
+            Without<IgnoreVirtualTime>,
+            Without<InvalidAudioSource>,
+        ),
+    >,
+    mut last_speed: Local<Option<f32>>,
+    ffi_calls: Res<FmodFfiCalls>,
+    mut errors: FmodErrorReporter,
+) {
+    if !settings.scale_pitch {
+        return;
+    }
+
+    let ffi = FfiScope::new(
+        info_span!("fmod::scale_pitch_with_virtual_time", ffi_calls = Empty),
+        &ffi_calls,
+    );
+
+    let speed = time.relative_speed();
+    let sources_to_update = if *last_speed == Some(speed) {
+        added_sources.iter().collect::<Vec<_>>()
+    } else {
+        sources.iter().collect()
+    };
+    *last_speed = Some(speed);
+
+    for (entity, source) in sources_to_update {
+        if let Err(e) = ffi.call(|| source.set_pitch(speed)) {
+            errors.report(FmodError::new(e).with_entity(entity));
+        }
+    }
+}
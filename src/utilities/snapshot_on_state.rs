@@ -0,0 +1,77 @@
+use crate::FmodStudio;
+use crate::components::snapshot::Snapshot;
+use crate::fmod_error::FmodErrorReporter;
+use bevy::app::{App, Plugin};
+use bevy::prelude::{Commands, DespawnOnExit, OnEnter, Res, States};
+use libfmod::StopMode;
+
+/// Activates an FMOD snapshot while the app is in the given [States] value.
+///
+/// Entering the state spawns an entity with a [Snapshot], and exiting the state despawns it,
+/// which stops the snapshot with the configured [StopMode].
+///
+/// ```
+/// # use bevy::prelude::States;
+/// # use bevy_fmod::utilities::SnapshotOnState;
+/// #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+/// enum GameState {
+///     #[default]
+///     Playing,
+///     Paused,
+/// }
+///
+/// let plugin = SnapshotOnState::new(GameState::Paused, "snapshot:/Pause");
+/// ```
+pub struct SnapshotOnState<S: States> {
+    state: S,
+    path: &'static str,
+    stop_mode: StopMode,
+}
+
+impl<S: States> SnapshotOnState<S> {
+    /// Activates the snapshot at `path` while in `state`.
+    #[must_use]
+    pub fn new(state: S, path: &'static str) -> Self {
+        SnapshotOnState {
+            state,
+            path,
+            stop_mode: StopMode::AllowFadeout,
+        }
+    }
+
+    /// Sets the [StopMode] used when leaving the state. Defaults to [StopMode::AllowFadeout].
+    #[must_use]
+    pub fn with_stop_mode(mut self, stop_mode: StopMode) -> Self {
+        self.stop_mode = stop_mode;
+        self
+    }
+}
+
+impl<S: States> Plugin for SnapshotOnState<S> {
+    fn build(&self, app: &mut App) {
+        let state = self.state.clone();
+        let path = self.path;
+        let stop_mode = self.stop_mode;
+
+        app.add_systems(
+            OnEnter(self.state.clone()),
+            move |mut commands: Commands,
+                  studio: Res<FmodStudio>,
+                  mut errors: FmodErrorReporter| match Snapshot::from_path(
+                &studio, path,
+            ) {
+                Ok(snapshot) => {
+                    commands.spawn((
+                        snapshot.with_stop_mode(stop_mode),
+                        DespawnOnExit(state.clone()),
+                    ));
+                }
+                Err(e) => errors.report(e),
+            },
+        );
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
@@ -2,7 +2,9 @@
 // Verifies AudioSource and AudioListener 3D attribute updates
 
 use bevy::prelude::*;
-use bevy_fmod::components::{AudioListener, AudioSource, Velocity};
+use bevy_fmod::components::{
+    AudioListener, AudioSource, ListenerAttenuationTarget, ListenerWeight, Velocity,
+};
 use bevy_fmod::{FmodPlugin, FmodStudio};
 use libfmod::StopMode;
 
@@ -180,3 +182,129 @@ fn test_multiple_audio_sources() {
         }
     }
 }
+
+#[test]
+fn test_multiple_audio_listeners() {
+    // Test that every AudioListener gets its own FMOD listener
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let listener1 = app.world_mut().spawn((
+        AudioListener,
+        Transform::from_xyz(-5.0, 0.0, 0.0),
+    )).id();
+    app.world_mut().spawn((
+        AudioListener,
+        ListenerWeight(0.5),
+        Transform::from_xyz(5.0, 0.0, 0.0),
+    ));
+
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    assert_eq!(studio.get_num_listeners().expect("Failed to get listener count"), 2);
+    assert_eq!(studio.get_listener_weight(1).expect("Failed to get listener weight"), 0.5);
+
+    // Despawning a listener reduces the listener count
+    app.world_mut().despawn(listener1);
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    assert_eq!(studio.get_num_listeners().expect("Failed to get listener count"), 1);
+    assert_eq!(studio.get_listener_weight(0).expect("Failed to get listener weight"), 0.5);
+
+    println!("Multiple AudioListeners are kept in sync with FMOD");
+}
+
+#[test]
+fn test_audio_listener_attenuation_target() {
+    // Test that the attenuation position follows the target entity
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let player = app.world_mut().spawn(Transform::from_xyz(3.0, 0.0, -4.0)).id();
+    app.world_mut().spawn((
+        AudioListener,
+        ListenerAttenuationTarget(player),
+        Transform::from_xyz(0.0, 2.0, 6.0),
+    ));
+
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let (attributes, attenuation_position) = studio
+        .get_listener_attributes(0)
+        .expect("Failed to get listener attributes");
+
+    assert_eq!(attributes.position.z, 6.0);
+    assert_eq!(attenuation_position.x, 3.0);
+    assert_eq!(attenuation_position.z, -4.0);
+
+    println!("AudioListener attenuation position follows target");
+}
+
+#[test]
+fn test_unchanged_audio_sources_are_not_resent() {
+    // Test that only sources that moved get their 3D attributes sent again
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_desc) = studio.get_event("event:/test") else {
+        println!("Skipping 3D test - no valid events in banks");
+        return;
+    };
+    let event_instance = event_desc.create_instance().expect("Failed to create instance");
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            Transform::from_xyz(10.0, 5.0, 3.0),
+        ))
+        .id();
+    app.update();
+
+    // Change the attributes behind the back of the AudioSource
+    let mut attributes = event_instance
+        .get_3d_attributes()
+        .expect("Failed to get 3D attributes");
+    assert_eq!(attributes.position.x, 10.0);
+    attributes.position.x = -100.0;
+    event_instance
+        .set_3d_attributes(attributes)
+        .expect("Failed to set 3D attributes");
+
+    // A static source is not sent again
+    app.update();
+    let attributes = event_instance
+        .get_3d_attributes()
+        .expect("Failed to get 3D attributes");
+    assert_eq!(attributes.position.x, -100.0);
+
+    // A moved source is
+    app.world_mut().get_mut::<Transform>(entity).unwrap().translation.x = 20.0;
+    app.update();
+    let attributes = event_instance
+        .get_3d_attributes()
+        .expect("Failed to get 3D attributes");
+    assert_eq!(attributes.position.x, 20.0);
+}
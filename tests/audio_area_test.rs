@@ -0,0 +1,194 @@
+// Test the AudioArea component
+// Verifies nearest points of area shapes and emitter placement relative to the listener
+
+use bevy::prelude::*;
+use bevy_fmod::components::{AreaShape, AudioArea, AudioListener, AudioSource, EmitterPosition};
+use bevy_fmod::{FmodPlugin, FmodStudio};
+use libfmod::StopMode;
+
+#[test]
+fn test_polyline_nearest_point() {
+    let river = AreaShape::Polyline(vec![
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(10.0, 0.0, 0.0),
+        Vec3::new(10.0, 0.0, 10.0),
+    ]);
+
+    assert_eq!(
+        river.nearest_point(Vec3::new(5.0, 0.0, -3.0)),
+        (Vec3::new(5.0, 0.0, 0.0), false)
+    );
+    assert_eq!(
+        river.nearest_point(Vec3::new(14.0, 0.0, 6.0)),
+        (Vec3::new(10.0, 0.0, 6.0), false)
+    );
+    assert_eq!(
+        river.nearest_point(Vec3::new(-4.0, 0.0, 0.0)),
+        (Vec3::ZERO, false)
+    );
+}
+
+#[test]
+fn test_box_and_sphere_nearest_point() {
+    let area = AreaShape::Box {
+        half_size: Vec3::new(2.0, 1.0, 2.0),
+    };
+    assert_eq!(
+        area.nearest_point(Vec3::new(5.0, 0.5, 0.0)),
+        (Vec3::new(2.0, 0.5, 0.0), false)
+    );
+    assert_eq!(
+        area.nearest_point(Vec3::new(1.0, 0.5, 0.0)),
+        (Vec3::new(1.0, 0.5, 0.0), true)
+    );
+
+    let lake = AreaShape::Sphere { radius: 5.0 };
+    assert_eq!(
+        lake.nearest_point(Vec3::new(0.0, 0.0, 10.0)),
+        (Vec3::new(0.0, 0.0, 5.0), false)
+    );
+    assert_eq!(
+        lake.nearest_point(Vec3::new(0.0, 0.0, 3.0)),
+        (Vec3::new(0.0, 0.0, 3.0), true)
+    );
+}
+
+#[cfg(feature = "geometry")]
+#[test]
+fn test_mesh_nearest_point() {
+    let shape = AreaShape::from_mesh(&Mesh::from(Cuboid::new(2.0, 2.0, 2.0))).unwrap();
+
+    let (nearest, inside) = shape.nearest_point(Vec3::new(4.0, 0.0, 0.0));
+    assert!(nearest.distance(Vec3::new(1.0, 0.0, 0.0)) < 1e-5);
+    assert!(!inside);
+
+    assert_eq!(
+        shape.nearest_point(Vec3::new(0.5, 0.0, 0.0)),
+        (Vec3::new(0.5, 0.0, 0.0), true)
+    );
+}
+
+#[test]
+fn test_audio_area_inside() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping audio area test - no valid events in banks");
+        return;
+    };
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+
+    let listener = app
+        .world_mut()
+        .spawn((AudioListener, Transform::from_xyz(20.0, 0.0, 0.0)))
+        .id();
+    let area = app
+        .world_mut()
+        .spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            AudioArea::new(AreaShape::Sphere { radius: 5.0 }),
+            Transform::from_xyz(10.0, 0.0, 0.0),
+        ))
+        .id();
+
+    app.update();
+    assert!(!app.world().get::<AudioArea>(area).unwrap().is_inside());
+
+    app.world_mut()
+        .get_mut::<Transform>(listener)
+        .unwrap()
+        .translation
+        .x = 12.0;
+    app.update();
+    assert!(app.world().get::<AudioArea>(area).unwrap().is_inside());
+}
+
+#[test]
+fn test_audio_area_emitter_position() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping audio area test - no valid events in banks");
+        return;
+    };
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+
+    let listener = app
+        .world_mut()
+        .spawn((AudioListener, Transform::from_xyz(5.0, 0.0, -3.0)))
+        .id();
+    let area = app
+        .world_mut()
+        .spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            AudioArea::new(AreaShape::Polyline(vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 0.0),
+            ])),
+            Transform::default(),
+        ))
+        .id();
+
+    app.update();
+    assert_eq!(
+        app.world().get::<EmitterPosition>(area),
+        Some(&EmitterPosition(Vec3::new(5.0, 0.0, 0.0)))
+    );
+
+    // Change the attributes behind the back of the area
+    let mut attributes = event_instance
+        .get_3d_attributes()
+        .expect("Failed to get 3D attributes");
+    assert_eq!(attributes.position.x, 5.0);
+    attributes.position.x = -100.0;
+    event_instance
+        .set_3d_attributes(attributes)
+        .expect("Failed to set 3D attributes");
+
+    // The emitter didn't move, so the attributes are not sent again
+    app.update();
+    let attributes = event_instance
+        .get_3d_attributes()
+        .expect("Failed to get 3D attributes");
+    assert_eq!(attributes.position.x, -100.0);
+
+    // Moving the listener along the river moves the emitter
+    app.world_mut()
+        .get_mut::<Transform>(listener)
+        .unwrap()
+        .translation
+        .x = 8.0;
+    app.update();
+    assert_eq!(
+        app.world().get::<EmitterPosition>(area),
+        Some(&EmitterPosition(Vec3::new(8.0, 0.0, 0.0)))
+    );
+    let attributes = event_instance
+        .get_3d_attributes()
+        .expect("Failed to get 3D attributes");
+    assert_eq!(attributes.position.x, 8.0);
+}
@@ -0,0 +1,102 @@
+// Test the AudioOccluder component
+// Verifies that FMOD geometry is built from meshes, rebuilt when they change and released on
+// despawn
+#![cfg(feature = "geometry")]
+
+use bevy::asset::{AssetPlugin, RenderAssetUsages};
+use bevy::mesh::PrimitiveTopology;
+use bevy::prelude::*;
+use bevy_fmod::FmodPlugin;
+use bevy_fmod::components::AudioOccluder;
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins((AssetPlugin::default(), TransformPlugin))
+        .init_asset::<Mesh>()
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+    app
+}
+
+#[test]
+fn test_audio_occluder_builds_geometry() {
+    let mut app = create_app();
+
+    let mesh = app
+        .world_mut()
+        .resource_mut::<Assets<Mesh>>()
+        .add(Cuboid::new(4.0, 3.0, 0.2));
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            AudioOccluder::new(mesh).with_occlusion(0.8, 0.5),
+            Transform::from_xyz(0.0, 1.5, -4.0),
+        ))
+        .id();
+
+    app.update();
+
+    let occluder = app.world().get::<AudioOccluder>(entity).unwrap();
+    assert!(occluder.geometry().is_some());
+
+    // Moving the occluder only updates the geometry's transform
+    app.world_mut()
+        .get_mut::<Transform>(entity)
+        .unwrap()
+        .translation
+        .x = 2.0;
+    app.update();
+
+    app.world_mut().despawn(entity);
+    app.update();
+}
+
+#[test]
+fn test_audio_occluder_waits_for_mesh() {
+    let mut app = create_app();
+
+    let entity = app
+        .world_mut()
+        .spawn((AudioOccluder::new(Handle::default()), Transform::default()))
+        .id();
+
+    app.update();
+
+    let occluder = app.world().get::<AudioOccluder>(entity).unwrap();
+    assert!(occluder.geometry().is_none());
+}
+
+#[test]
+fn test_audio_occluder_rebuilds_on_mesh_change() {
+    let mut app = create_app();
+
+    // Line lists have no triangles, so building the geometry fails
+    let lines = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+        );
+    let mesh = app.world_mut().resource_mut::<Assets<Mesh>>().add(lines);
+
+    let entity = app
+        .world_mut()
+        .spawn((AudioOccluder::new(mesh.clone()), Transform::default()))
+        .id();
+
+    let geometry = |app: &App| app.world().get::<AudioOccluder>(entity).unwrap().geometry();
+
+    app.update();
+    assert!(geometry(&app).is_none());
+
+    // Fixing the mesh retries the failed occluder
+    app.world_mut()
+        .resource_mut::<Assets<Mesh>>()
+        .insert(&mesh, Cuboid::new(4.0, 3.0, 0.2).into())
+        .unwrap();
+    app.update();
+    assert!(geometry(&app).is_some());
+}
@@ -0,0 +1,128 @@
+// Test the AudioSettings utility
+// Verifies volume scaling, channel updates and persistence
+
+#[cfg(feature = "persistence")]
+use std::time::Duration;
+
+#[cfg(feature = "persistence")]
+use bevy::prelude::*;
+#[cfg(feature = "persistence")]
+use bevy_fmod::FmodPlugin;
+use bevy_fmod::utilities::{AudioSettings, VolumeScale};
+#[cfg(feature = "persistence")]
+use bevy_fmod::utilities::{AudioSettingsError, AudioSettingsPlugin};
+
+#[test]
+fn test_linear_volume_scale() {
+    let scale = VolumeScale::Linear;
+
+    assert_eq!(scale.gain(0.0), 0.0);
+    assert_eq!(scale.gain(0.5), 0.5);
+    assert_eq!(scale.gain(1.0), 1.0);
+    assert_eq!(scale.gain(2.0), 1.0, "Slider values should be clamped");
+}
+
+#[test]
+fn test_decibel_volume_scale() {
+    let scale = VolumeScale::Decibels { floor: -60.0 };
+
+    assert_eq!(scale.gain(0.0), 0.0, "Lowest slider position should be silent");
+    assert_eq!(scale.gain(1.0), 1.0, "Highest slider position should be 0 dB");
+
+    // Half way is -30 dB
+    let half = scale.gain(0.5);
+    assert!((half - 10f32.powf(-30.0 / 20.0)).abs() < 1e-6);
+}
+
+#[test]
+fn test_audio_settings_channels() {
+    let mut settings = AudioSettings::default()
+        .with_channel("Master", "vca:/Master", 1.0)
+        .with_channel("Music", "vca:/Music", 0.8);
+
+    assert_eq!(settings.volume("Music"), Some(0.8));
+
+    // Volumes are clamped like with set_volume
+    let clamped = AudioSettings::default().with_channel("Voice", "vca:/Voice", 1.5);
+    assert_eq!(clamped.volume("Voice"), Some(1.0));
+
+    settings.set_volume("Music", 0.25);
+    assert_eq!(settings.volume("Music"), Some(0.25));
+
+    // Unknown channels are ignored
+    settings.set_volume("Voice", 0.5);
+    assert_eq!(settings.volume("Voice"), None);
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_audio_settings_save_and_load() {
+    let settings = AudioSettings::default()
+        .with_channel("Master", "vca:/Master", 0.7)
+        .with_scale(VolumeScale::Decibels { floor: -48.0 });
+
+    let path = std::env::temp_dir().join("bevy_fmod_audio_settings_test.ron");
+    settings.save(&path).expect("Failed to save audio settings");
+
+    let loaded = AudioSettings::load(&path).expect("Failed to load audio settings");
+    assert_eq!(loaded, settings);
+
+    std::fs::remove_file(path).ok();
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_audio_settings_load_errors() {
+    let missing = std::env::temp_dir().join("bevy_fmod_audio_settings_missing.ron");
+    assert!(matches!(
+        AudioSettings::load(&missing),
+        Err(AudioSettingsError::Io(_))
+    ));
+
+    let invalid = std::env::temp_dir().join("bevy_fmod_audio_settings_invalid.ron");
+    std::fs::write(&invalid, "not settings").unwrap();
+    assert!(matches!(
+        AudioSettings::load(&invalid),
+        Err(AudioSettingsError::Parse(_))
+    ));
+
+    std::fs::remove_file(invalid).ok();
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_audio_settings_saved_after_delay_or_on_exit() {
+    let path = std::env::temp_dir().join("bevy_fmod_audio_settings_debounce_test.ron");
+    std::fs::remove_file(&path).ok();
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        FmodPlugin::new(&["tests/data/Master.bank", "tests/data/Master.strings.bank"]),
+        AudioSettingsPlugin::new(AudioSettings::default().with_channel(
+            "Master",
+            "vca:/Master",
+            1.0,
+        ))
+        .with_file(&path)
+        .with_save_delay(Duration::from_secs(60)),
+    ));
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<AudioSettings>()
+        .set_volume("Master", 0.4);
+    app.update();
+    assert!(
+        !path.exists(),
+        "Settings shouldn't be saved while they may still change"
+    );
+
+    app.world_mut().write_message(AppExit::Success);
+    app.update();
+
+    let saved = AudioSettings::load(&path).expect("Settings should be saved on exit");
+    assert_eq!(saved.volume("Master"), Some(0.4));
+
+    std::fs::remove_file(path).ok();
+}
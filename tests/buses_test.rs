@@ -0,0 +1,58 @@
+// Test the FmodBuses resource
+// Verifies bus caching and that queued changes are applied on update
+
+use bevy::prelude::*;
+use bevy_fmod::{FmodBuses, FmodPlugin, FmodStudio};
+
+#[test]
+fn test_buses_resource_caches_bus() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(FmodPlugin::new(&[
+        "tests/data/Master.bank",
+        "tests/data/Master.strings.bank",
+    ]));
+
+    app.world_mut()
+        .resource_scope(|world, mut buses: Mut<FmodBuses>| {
+            let studio = world.resource::<FmodStudio>();
+
+            let first = buses.get(studio, "bus:/").expect("Master bus should exist");
+            let second = buses.get(studio, "bus:/").expect("Master bus should be cached");
+            assert_eq!(first, second);
+
+            assert!(buses.get(studio, "bus:/DoesNotExist").is_err());
+        });
+}
+
+#[test]
+fn test_buses_resource_applies_queued_changes() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(FmodPlugin::new(&[
+        "tests/data/Master.bank",
+        "tests/data/Master.strings.bank",
+    ]));
+
+    {
+        let mut buses = app.world_mut().resource_mut::<FmodBuses>();
+        buses.set_mute("bus:/", true);
+        buses.set_paused("bus:/", true);
+    }
+
+    // Queued changes are applied in PostUpdate
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let master_bus = studio.get_bus("bus:/").expect("Master bus should exist");
+    assert!(master_bus.get_mute().expect("Should be able to get mute state"));
+    assert!(master_bus.get_paused().expect("Should be able to get paused state"));
+
+    let mut buses = app.world_mut().resource_mut::<FmodBuses>();
+    buses.set_mute("bus:/", false);
+    buses.set_paused("bus:/", false);
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let master_bus = studio.get_bus("bus:/").expect("Master bus should exist");
+    assert!(!master_bus.get_mute().expect("Should be able to get mute state"));
+    assert!(!master_bus.get_paused().expect("Should be able to get paused state"));
+}
@@ -0,0 +1,130 @@
+// Test typed identifier generation
+// Verifies GUIDs.txt parsing and the generated module layout
+
+use bevy_fmod::codegen::{Codegen, CodegenError, ParameterId};
+
+const GUIDS: &str = "\
+{0b8d7ef8-2e3b-4b0a-8c1e-8d7e3a4f9c10} bank:/Master
+{1c2d3e4f-5a6b-4c7d-8e9f-0a1b2c3d4e5f} bus:/
+{2d3e4f5a-6b7c-4d8e-9f0a-1b2c3d4e5f6a} bus:/SFX
+{3e4f5a6b-7c8d-4e9f-0a1b-2c3d4e5f6a7b} vca:/Music
+{4f5a6b7c-8d9e-4f0a-1b2c-3d4e5f6a7b8c} event:/Ambience/Forest
+{5a6b7c8d-9e0f-4a1b-2c3d-4e5f6a7b8c9d} event:/Ambience/Country Side
+{6b7c8d9e-0f1a-4b2c-3d4e-5f6a7b8c9d0e} snapshot:/Underwater
+{7c8d9e0f-1a2b-4c3d-4e5f-6a7b8c9d0e1f} parameter:/Weather
+{8d9e0f1a-2b3c-4d4e-5f6a-7b8c9d0e1f2a} event:/UI/Type/Click
+{9e0f1a2b-3c4d-4e5f-6a7b-8c9d0e1f2a3b} event:/Crate/Break
+{0f1a2b3c-4d5e-4f6a-7b8c-9d0e1f2a3b4c} event:/Super/Jump
+{1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d} event:/Self/Talk
+";
+
+#[test]
+fn test_codegen_generates_typed_constants() {
+    let source = Codegen::from_guids_str(GUIDS).generate().unwrap();
+
+    assert!(source.contains("pub mod events {"));
+    assert!(source.contains("pub mod ambience {"));
+    assert!(source.contains("pub const FOREST: ::bevy_fmod::codegen::EventId"));
+    assert!(source.contains("pub const COUNTRY_SIDE: ::bevy_fmod::codegen::EventId"));
+    assert!(source.contains("guid: \"{4f5a6b7c-8d9e-4f0a-1b2c-3d4e5f6a7b8c}\""));
+    assert!(source.contains("pub const UNDERWATER: ::bevy_fmod::codegen::SnapshotId"));
+    assert!(source.contains("pub const MASTER: ::bevy_fmod::codegen::BusId"));
+    assert!(source.contains("pub const SFX: ::bevy_fmod::codegen::BusId"));
+    assert!(source.contains("pub const MUSIC: ::bevy_fmod::codegen::VcaId"));
+    assert!(source.contains("pub const WEATHER: ::bevy_fmod::codegen::ParameterId"));
+
+    // Keywords are escaped and banks are skipped
+    assert!(source.contains("pub mod r#type {"));
+    assert!(source.contains("pub mod crate_ {"));
+    assert!(source.contains("pub mod super_ {"));
+    assert!(source.contains("pub mod self_ {"));
+    assert!(
+        !source.contains("r#crate") && !source.contains("r#super") && !source.contains("r#self")
+    );
+    assert!(!source.contains("bank:/"));
+}
+
+#[test]
+fn test_codegen_ignores_malformed_lines() {
+    let source = Codegen::from_guids_str("not a guid line\nevent:/Missing/Guid\n")
+        .generate()
+        .unwrap();
+
+    assert!(!source.contains("pub mod events"));
+}
+
+#[test]
+fn test_codegen_keeps_non_ascii_names() {
+    let source = Codegen::from_guids_str(
+        "{4f5a6b7c-8d9e-4f0a-1b2c-3d4e5f6a7b8c} event:/Música/Ça va\n\
+         {5a6b7c8d-9e0f-4a1b-2c3d-4e5f6a7b8c9d} event:/音楽/2D Loop\n",
+    )
+    .generate()
+    .unwrap();
+
+    assert!(source.contains("pub mod música {"));
+    assert!(source.contains("pub const ÇA_VA: ::bevy_fmod::codegen::EventId"));
+    assert!(source.contains("pub mod 音楽 {"));
+    assert!(source.contains("pub const _2D_LOOP: ::bevy_fmod::codegen::EventId"));
+}
+
+#[test]
+fn test_codegen_hashes_empty_names() {
+    let guids = "{4f5a6b7c-8d9e-4f0a-1b2c-3d4e5f6a7b8c} event:/!!!/???\n";
+    let source = Codegen::from_guids_str(guids).generate().unwrap();
+
+    assert!(!source.contains("pub mod _ {"));
+    let module = source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("pub mod _"))
+        .unwrap();
+    assert!(module.strip_suffix(" {").unwrap().len() == 8);
+
+    // The name doesn't depend on the other entries
+    let more = format!("{{5a6b7c8d-9e0f-4a1b-2c3d-4e5f6a7b8c9d}} event:/&&&/Click\n{guids}");
+    let other = Codegen::from_guids_str(&more).generate().unwrap();
+    assert!(other.contains(&format!("pub mod _{module}")));
+}
+
+#[test]
+fn test_codegen_reports_duplicate_identifiers() {
+    let constants = Codegen::from_guids_str(
+        "{4f5a6b7c-8d9e-4f0a-1b2c-3d4e5f6a7b8c} event:/Ambience/Country Side\n\
+         {5a6b7c8d-9e0f-4a1b-2c3d-4e5f6a7b8c9d} event:/Ambience/country-side\n",
+    )
+    .generate();
+
+    let Err(CodegenError::DuplicateIdentifier { identifier, paths }) = constants else {
+        panic!("Expected a duplicate identifier error");
+    };
+    assert_eq!(identifier, "COUNTRY_SIDE");
+    assert_eq!(
+        paths,
+        [
+            "event:/Ambience/Country Side",
+            "event:/Ambience/country-side"
+        ]
+    );
+
+    let modules = Codegen::from_guids_str(
+        "{4f5a6b7c-8d9e-4f0a-1b2c-3d4e5f6a7b8c} event:/Ui Sounds/Click\n\
+         {5a6b7c8d-9e0f-4a1b-2c3d-4e5f6a7b8c9d} event:/UI_sounds/Hover\n",
+    )
+    .generate();
+
+    let Err(CodegenError::DuplicateIdentifier { identifier, paths }) = modules else {
+        panic!("Expected a duplicate identifier error");
+    };
+    assert_eq!(identifier, "ui_sounds");
+    assert_eq!(paths, ["event:/Ui Sounds", "event:/UI_sounds"]);
+}
+
+#[test]
+fn test_parameter_id_name() {
+    let parameter = ParameterId {
+        path: "parameter:/Weather",
+        guid: "{7c8d9e0f-1a2b-4c3d-4e5f-6a7b8c9d0e1f}",
+    };
+
+    assert_eq!(parameter.name(), "Weather");
+}
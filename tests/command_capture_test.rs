@@ -0,0 +1,78 @@
+// Test the command capture and replay
+// Verifies that a capture recorded at runtime can be replayed without sound output
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_fmod::components::AudioSource;
+use bevy_fmod::{CommandReplayRunner, FmodCommandCapture, FmodPlugin, FmodStudio};
+use libfmod::StopMode;
+
+#[test]
+fn test_command_capture_and_replay() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let path = std::env::temp_dir().join("bevy_fmod_command_capture_test.cmd.cap");
+    app.world_mut()
+        .resource_mut::<FmodCommandCapture>()
+        .start(&path, true);
+
+    // The capture only starts once the queued start was applied
+    assert!(!app.world().resource::<FmodCommandCapture>().is_capturing());
+    app.update();
+
+    let capture = app.world().resource::<FmodCommandCapture>();
+    assert!(capture.is_capturing());
+    assert_eq!(capture.path(), Some(path.as_path()));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let has_event = studio.get_event("event:/test").is_ok();
+    if let Ok(event_description) = studio.get_event("event:/test") {
+        let event_instance = event_description
+            .create_instance()
+            .expect("Failed to create instance");
+        event_instance.start().expect("Failed to start instance");
+
+        app.world_mut().spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            Transform::from_xyz(1.0, 0.0, 0.0),
+        ));
+    }
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    app.world_mut().resource_mut::<FmodCommandCapture>().stop();
+    app.update();
+    assert!(!app.world().resource::<FmodCommandCapture>().is_capturing());
+
+    let runner = CommandReplayRunner::new(&path).expect("Failed to load command replay");
+    assert!(runner.replay().get_command_count().unwrap() > 0);
+    assert!(
+        runner
+            .run(Duration::from_secs(10))
+            .expect("Failed to run command replay")
+    );
+
+    // The replay keeps the event instance it recreated
+    if has_event {
+        let event_description = runner
+            .studio()
+            .get_event("event:/test")
+            .expect("Failed to get replayed event");
+        assert_eq!(event_description.get_instance_count().unwrap(), 1);
+    }
+
+    drop(runner);
+    let _ = std::fs::remove_file(path);
+}
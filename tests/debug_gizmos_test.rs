@@ -0,0 +1,88 @@
+// Test the FmodDebugGizmosPlugin
+// Verifies what gizmos are drawn and that they can be toggled at runtime
+#![cfg(feature = "debug-gizmos")]
+
+use std::any::TypeId;
+
+use bevy::asset::AssetPlugin;
+use bevy::gizmos::config::GizmoConfigStore;
+use bevy::gizmos::{GizmoAsset, GizmoPlugin};
+use bevy::prelude::*;
+use bevy_fmod::components::{AudioListener, AudioSource, Velocity};
+use bevy_fmod::{FmodDebugGizmosPlugin, FmodGizmos, FmodPlugin, FmodStudio};
+use libfmod::StopMode;
+
+#[test]
+fn test_debug_gizmos_toggle() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins((AssetPlugin::default(), TransformPlugin, GizmoPlugin))
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(FmodDebugGizmosPlugin);
+
+    app.world_mut()
+        .spawn((AudioListener, Velocity::default(), Transform::default()));
+
+    let studio = app.world().resource::<FmodStudio>();
+    if let Ok(event_description) = studio.get_event("event:/test") {
+        let event_instance = event_description
+            .create_instance()
+            .expect("Failed to create instance");
+
+        app.world_mut().spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            Transform::from_xyz(3.0, 0.0, 0.0),
+        ));
+    }
+
+    // Gizmos are collected into an asset at the end of every frame, and assets of hidden gizmos
+    // are dropped one frame later
+    let update = |app: &mut App| {
+        app.update();
+        app.update();
+    };
+
+    update(&mut app);
+    let all = drawn_vertices(&app);
+    assert!(all > 0);
+
+    // Disabling the group hides sources and listeners
+    configure(&mut app, |config, _| config.enabled = false);
+    update(&mut app);
+    assert_eq!(drawn_vertices(&app), 0);
+
+    // Without listener axes and velocities, only the spheres are left
+    configure(&mut app, |config, fmod_gizmos| {
+        config.enabled = true;
+        fmod_gizmos.listeners = false;
+        fmod_gizmos.velocity = false;
+    });
+    update(&mut app);
+    let spheres = drawn_vertices(&app);
+    assert!(spheres > 0 && spheres < all);
+}
+
+fn configure(app: &mut App, f: impl FnOnce(&mut GizmoConfig, &mut FmodGizmos)) {
+    let mut config_store = app.world_mut().resource_mut::<GizmoConfigStore>();
+    let (config, fmod_gizmos) = config_store.config_mut::<FmodGizmos>();
+    f(config, fmod_gizmos);
+}
+
+/// Number of line vertices drawn by the FmodDebugGizmosPlugin in the last frame.
+fn drawn_vertices(app: &App) -> usize {
+    app.world()
+        .resource::<Assets<GizmoAsset>>()
+        .iter()
+        .filter(|(_, gizmo)| gizmo.config_typeid() == TypeId::of::<FmodGizmos>())
+        .map(|(_, gizmo)| {
+            let buffer = gizmo.buffer();
+            buffer.list_positions.len() + buffer.strip_positions.len()
+        })
+        .sum()
+}
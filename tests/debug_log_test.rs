@@ -0,0 +1,106 @@
+// Test the FMOD debug logging
+// Verifies that FMOD initializes with debug output forwarded into bevy::log at the right levels
+
+use std::ffi::CString;
+use std::sync::{Arc, Mutex};
+
+use bevy::log::tracing::span::{Attributes, Id, Record};
+use bevy::log::tracing::subscriber::with_default;
+use bevy::log::tracing::{Event, Level, Metadata, Subscriber};
+use bevy::prelude::*;
+use bevy_fmod::{FmodDebugLevel, FmodDebugLog, FmodPlugin, FmodStudio};
+use libfmod::ffi::{
+    FMOD_DEBUG_LEVEL_ERROR, FMOD_DEBUG_LEVEL_LOG, FMOD_DEBUG_LEVEL_WARNING, FMOD_DEBUG_TYPE_FILE,
+    FMOD_DEBUG_TYPE_TRACE, FMOD_OK,
+};
+
+/// Records the target and level of every event.
+#[derive(Clone, Default)]
+struct Events(Arc<Mutex<Vec<(String, Level)>>>);
+
+impl Subscriber for Events {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        self.0
+            .lock()
+            .unwrap()
+            .push((metadata.target().to_string(), *metadata.level()));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_debug_log() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(
+        FmodPlugin::new(&["tests/data/Master.bank", "tests/data/Master.strings.bank"])
+            .with_debug_log(FmodDebugLog {
+                level: FmodDebugLevel::Log,
+                file: true,
+                ..Default::default()
+            }),
+    );
+
+    app.update();
+
+    // Without the logging FMOD libraries, FMOD still initializes without debug output
+    assert!(app.world().resource::<FmodStudio>().is_valid());
+}
+
+#[test]
+fn test_debug_log_levels() {
+    let callback = FmodDebugLog::callback().unwrap();
+    let file = CString::new("fmod_studio_impl.cpp").unwrap();
+    let function = CString::new("Studio::System::update").unwrap();
+    let message = CString::new("Message\n").unwrap();
+
+    let events = Events::default();
+    with_default(events.clone(), || {
+        for flags in [
+            FMOD_DEBUG_LEVEL_ERROR,
+            FMOD_DEBUG_LEVEL_WARNING,
+            FMOD_DEBUG_LEVEL_LOG,
+            FMOD_DEBUG_LEVEL_LOG | FMOD_DEBUG_TYPE_FILE,
+            FMOD_DEBUG_LEVEL_LOG | FMOD_DEBUG_TYPE_TRACE,
+        ] {
+            let result = unsafe {
+                callback(
+                    flags,
+                    file.as_ptr(),
+                    42,
+                    function.as_ptr(),
+                    message.as_ptr(),
+                )
+            };
+            assert_eq!(result, FMOD_OK);
+        }
+    });
+
+    let events = events.0.lock().unwrap();
+    assert!(events.iter().all(|(target, _)| target == "fmod"));
+    assert_eq!(
+        events.iter().map(|(_, level)| *level).collect::<Vec<_>>(),
+        [
+            Level::ERROR,
+            Level::WARN,
+            Level::INFO,
+            Level::DEBUG,
+            Level::TRACE
+        ]
+    );
+}
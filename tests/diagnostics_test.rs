@@ -0,0 +1,71 @@
+// Test the FmodDiagnosticsPlugin
+// Verifies that FMOD performance metrics are measured as Bevy diagnostics
+
+use bevy::diagnostic::{DiagnosticsPlugin, DiagnosticsStore};
+use bevy::prelude::*;
+use bevy_fmod::{FmodDiagnosticsPlugin, FmodPlugin, FmodStudio};
+
+#[test]
+fn test_fmod_diagnostics() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(DiagnosticsPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(FmodDiagnosticsPlugin);
+
+    app.update();
+
+    let diagnostics = app.world().resource::<DiagnosticsStore>();
+
+    for path in [
+        FmodDiagnosticsPlugin::STUDIO_CPU,
+        FmodDiagnosticsPlugin::MEMORY,
+        FmodDiagnosticsPlugin::CHANNELS,
+        FmodDiagnosticsPlugin::COMMAND_QUEUE,
+    ] {
+        let diagnostic = diagnostics.get(&path).expect("Diagnostic not registered");
+        assert!(diagnostic.value().is_some(), "No measurement for {}", path);
+    }
+
+    // Nothing is playing
+    let channels = diagnostics.get(&FmodDiagnosticsPlugin::CHANNELS).unwrap();
+    assert_eq!(channels.value(), Some(0.0));
+
+    let stalls = diagnostics
+        .get(&FmodDiagnosticsPlugin::COMMAND_QUEUE_STALLS)
+        .unwrap();
+    assert_eq!(stalls.value(), Some(0.0));
+}
+
+#[test]
+fn test_fmod_diagnostics_keep_buffer_peaks() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(DiagnosticsPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(FmodDiagnosticsPlugin);
+
+    app.update();
+
+    let peak_usage = |app: &App| {
+        app.world()
+            .resource::<FmodStudio>()
+            .get_buffer_usage()
+            .expect("Failed to get buffer usage")
+            .studiocommandqueue
+            .peakusage
+    };
+    let peak = peak_usage(&app);
+
+    // Measuring must not reset the peak usage FMOD tracks since startup
+    for _ in 0..3 {
+        app.update();
+        assert!(peak_usage(&app) >= peak);
+    }
+}
@@ -0,0 +1,188 @@
+// Test distance culling of audio sources
+// Verifies that sources out of range are culled and restored with hysteresis
+
+use bevy::prelude::*;
+use bevy_fmod::components::{AudioListener, AudioSource};
+use bevy_fmod::{
+    CullingMode, DistanceCulling, DistanceCullingPlugin, DistanceCullingSettings, FmodPlugin,
+    FmodStudio,
+};
+use libfmod::{PlaybackState, StopMode};
+
+/// Returns the app with a listener and a playing source within the max distance of event:/test.
+fn culling_app(mode: CullingMode) -> Option<(App, Entity, f32)> {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(DistanceCullingPlugin::new(DistanceCullingSettings {
+            mode,
+            hysteresis: 0.5,
+        }));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping culling test - no valid events in banks");
+        return None;
+    };
+    let (true, Ok((_, max_distance))) = (
+        event_description.is_3d().unwrap_or(false),
+        event_description.get_min_max_distance(),
+    ) else {
+        println!("Skipping culling test - test event is not 3D");
+        return None;
+    };
+
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+    event_instance.start().expect("Failed to start instance");
+
+    app.world_mut().spawn((AudioListener, Transform::default()));
+    let source = app
+        .world_mut()
+        .spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            Transform::from_xyz(max_distance * 0.5, 0.0, 0.0),
+        ))
+        .id();
+
+    // Let FMOD start the event before moving it out of range
+    app.update();
+    Some((app, source, max_distance))
+}
+
+fn move_source(app: &mut App, source: Entity, x: f32) {
+    app.world_mut()
+        .get_mut::<Transform>(source)
+        .unwrap()
+        .translation
+        .x = x;
+    app.update();
+}
+
+fn playback_state(app: &App, source: Entity) -> PlaybackState {
+    app.world()
+        .get::<AudioSource>(source)
+        .unwrap()
+        .get_playback_state()
+        .expect("Failed to get playback state")
+}
+
+fn test_culling(mode: CullingMode) {
+    let Some((mut app, source, max_distance)) = culling_app(mode) else {
+        return;
+    };
+
+    let is_culled = |app: &App| {
+        app.world()
+            .get::<DistanceCulling>(source)
+            .unwrap()
+            .is_culled()
+    };
+    assert!(!is_culled(&app));
+
+    move_source(&mut app, source, max_distance * 2.0);
+    assert!(is_culled(&app));
+
+    // Within the hysteresis, the source stays culled
+    move_source(&mut app, source, max_distance * 1.2);
+    assert!(is_culled(&app));
+
+    move_source(&mut app, source, max_distance * 0.5);
+    assert!(!is_culled(&app));
+
+    // Within the hysteresis, the source stays audible
+    move_source(&mut app, source, max_distance * 1.2);
+    assert!(!is_culled(&app));
+}
+
+#[test]
+fn test_distance_culling_pause() {
+    test_culling(CullingMode::Pause);
+}
+
+#[test]
+fn test_distance_culling_stop() {
+    test_culling(CullingMode::Stop);
+}
+
+#[test]
+fn test_distance_culling_keep_stopped() {
+    let Some((mut app, source, max_distance)) = culling_app(CullingMode::Stop) else {
+        return;
+    };
+
+    move_source(&mut app, source, max_distance * 2.0);
+    assert!(matches!(
+        playback_state(&app, source),
+        PlaybackState::Stopped
+    ));
+
+    // The game stops the source while it is culled
+    app.world_mut()
+        .get_mut::<DistanceCulling>(source)
+        .unwrap()
+        .keep_stopped();
+
+    move_source(&mut app, source, max_distance * 0.5);
+    assert!(matches!(
+        playback_state(&app, source),
+        PlaybackState::Stopped
+    ));
+    assert!(
+        !app.world()
+            .get::<DistanceCulling>(source)
+            .unwrap()
+            .is_culled()
+    );
+}
+
+#[test]
+fn test_distance_culling_ignores_idle_sources() {
+    let Some((mut app, source, max_distance)) = culling_app(CullingMode::Pause) else {
+        return;
+    };
+
+    app.world()
+        .get::<AudioSource>(source)
+        .unwrap()
+        .stop(StopMode::Immediate)
+        .expect("Failed to stop instance");
+    app.update();
+
+    move_source(&mut app, source, max_distance * 2.0);
+    assert!(
+        !app.world()
+            .get::<DistanceCulling>(source)
+            .unwrap()
+            .is_culled()
+    );
+
+    // Started out of range, the source keeps playing
+    app.world()
+        .get::<AudioSource>(source)
+        .unwrap()
+        .start()
+        .expect("Failed to start instance");
+    move_source(&mut app, source, max_distance * 3.0);
+    assert!(
+        !app.world()
+            .get::<DistanceCulling>(source)
+            .unwrap()
+            .is_culled()
+    );
+    assert!(
+        !app.world()
+            .get::<AudioSource>(source)
+            .unwrap()
+            .get_paused()
+            .unwrap()
+    );
+}
@@ -0,0 +1,160 @@
+// Test the error reporting of FMOD errors
+// Verifies that errors are sent as messages and passed to the FmodErrorHandler instead of panicking
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy::ecs::error::{BevyError, ErrorContext};
+use bevy::prelude::*;
+use bevy_fmod::components::{AreaShape, AudioArea, AudioListener, AudioSource};
+use bevy_fmod::{FmodBuses, FmodError, FmodErrorHandler, FmodErrorMessage, FmodPlugin, FmodStudio};
+use libfmod::{Error, StopMode};
+
+static HANDLED_ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+fn count_error(_error: BevyError, _context: ErrorContext) {
+    HANDLED_ERRORS.fetch_add(1, Ordering::Relaxed);
+}
+
+#[test]
+fn test_error_message() {
+    let error = FmodError::new(Error::Fmod {
+        function: "FMOD_Studio_EventInstance_Start".to_string(),
+        code: 30,
+        message: "An invalid object handle was used.".to_string(),
+    })
+    .with_entity(Entity::PLACEHOLDER);
+
+    let message = error.message();
+    assert_eq!(message.entity, Some(Entity::PLACEHOLDER));
+    assert_eq!(
+        message.function.as_deref(),
+        Some("FMOD_Studio_EventInstance_Start")
+    );
+    assert_eq!(message.code, Some(30));
+    assert_eq!(error.code(), Some(30));
+}
+
+#[test]
+fn test_missing_parameter_reports_error() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping error test - no valid events in banks");
+        return;
+    };
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+
+    // The area sets its inside parameter, which event:/test doesn't have
+    app.world_mut().spawn((AudioListener, Transform::default()));
+    let entity = app
+        .world_mut()
+        .spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            AudioArea::new(AreaShape::Sphere { radius: 1.0 }).with_inside_parameter("Missing"),
+            Transform::default(),
+        ))
+        .id();
+    app.update();
+
+    let messages = app.world().resource::<Messages<FmodErrorMessage>>();
+    let mut cursor = messages.get_cursor();
+    let message = cursor
+        .read(messages)
+        .find(|message| message.entity == Some(entity))
+        .expect("Expected an error for the missing parameter");
+    assert!(message.code.is_some());
+}
+
+#[test]
+fn test_despawning_released_instance() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping error test - no valid events in banks");
+        return;
+    };
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            Transform::default(),
+        ))
+        .id();
+    app.update();
+
+    // Release the instance behind the back of the AudioSource
+    event_instance
+        .release()
+        .expect("Failed to release instance");
+    app.world()
+        .resource::<FmodStudio>()
+        .flush_commands()
+        .expect("Failed to flush commands");
+
+    // The hook skips the released instance instead of failing to stop it
+    app.world_mut().entity_mut(entity).despawn();
+    app.update();
+
+    let messages = app.world().resource::<Messages<FmodErrorMessage>>();
+    let mut cursor = messages.get_cursor();
+    assert!(
+        !cursor
+            .read(messages)
+            .any(|message| message.entity == Some(entity))
+    );
+}
+
+#[test]
+fn test_error_handler() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    // The default handler logs the error instead of panicking
+    app.world_mut()
+        .resource_mut::<FmodBuses>()
+        .set_volume("bus:/Missing", 0.5);
+    app.update();
+
+    app.insert_resource(FmodErrorHandler(count_error));
+    app.world_mut()
+        .resource_mut::<FmodBuses>()
+        .set_volume("bus:/Missing", 0.5);
+    app.update();
+
+    assert_eq!(HANDLED_ERRORS.load(Ordering::Relaxed), 1);
+
+    let messages = app.world().resource::<Messages<FmodErrorMessage>>();
+    let mut cursor = messages.get_cursor();
+    assert_eq!(cursor.read(messages).count(), 2);
+}
@@ -0,0 +1,95 @@
+// Test the InstanceReportPlugin
+// Verifies that instances of loaded events are counted and associated with their entities
+
+use bevy::prelude::*;
+use bevy_fmod::components::AudioSource;
+use bevy_fmod::{FmodPlugin, FmodStudio, InstanceReport, InstanceReportPlugin};
+use libfmod::StopMode;
+
+#[test]
+fn test_instance_report() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(InstanceReportPlugin::default());
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping instance report test - no valid events in banks");
+        return;
+    };
+
+    let mut entities = Vec::new();
+    for _ in 0..3 {
+        let event_instance = event_description
+            .create_instance()
+            .expect("Failed to create instance");
+        let entity = app
+            .world_mut()
+            .spawn(AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            })
+            .id();
+        entities.push(entity);
+    }
+
+    app.update();
+
+    let report = app.world().resource::<InstanceReport>();
+    let event = report.get("event:/test").expect("Event not in report");
+    assert_eq!(event.instances, 3);
+    assert_eq!(event.real_instances() + event.virtual_instances, 3);
+
+    let mut reported = event.entities.clone();
+    reported.sort();
+    entities.sort();
+    assert_eq!(reported, entities);
+}
+
+#[test]
+fn test_instance_report_without_strings_bank() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FmodPlugin::new(&["tests/data/Master.bank"]))
+        .add_plugins(InstanceReportPlugin::default());
+
+    // Without the strings bank, events can only be found through the bank
+    let studio = app.world().resource::<FmodStudio>();
+    let events = studio
+        .get_bank_list(studio.get_bank_count().expect("Failed to count banks"))
+        .expect("Failed to list banks")
+        .into_iter()
+        .flat_map(|bank| {
+            bank.get_event_list(bank.get_event_count().unwrap_or(0))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let Some(event_description) = events.first() else {
+        println!("Skipping instance report test - no events in Master.bank");
+        return;
+    };
+    let id = event_description.get_id().expect("Failed to get event id");
+
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+    let entity = app
+        .world_mut()
+        .spawn(AudioSource {
+            event_instance,
+            despawn_stop_mode: StopMode::Immediate,
+        })
+        .id();
+
+    app.update();
+
+    let report = app.world().resource::<InstanceReport>();
+    let event = report.get_by_id(id).expect("Event not in report");
+    assert_eq!(event.path, None);
+    assert_eq!(event.instances, 1);
+    assert_eq!(event.entities, vec![entity]);
+}
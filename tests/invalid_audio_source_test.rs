@@ -0,0 +1,194 @@
+// Test the validation of AudioSource event instances
+// Verifies that stale instances are marked invalid and recreated on request
+
+use bevy::prelude::*;
+use bevy_fmod::components::{AudioSource, InvalidAudioSource, RecreateAudioSource};
+use bevy_fmod::{FmodPlugin, FmodStudio};
+use libfmod::StopMode;
+
+fn setup_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+    app
+}
+
+fn spawn_and_release(app: &mut App, recreate: Option<RecreateAudioSource>) -> Option<Entity> {
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping invalid audio source test - no valid events in banks");
+        return None;
+    };
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+
+    let mut entity = app.world_mut().spawn((
+        AudioSource {
+            event_instance,
+            despawn_stop_mode: StopMode::Immediate,
+        },
+        Transform::default(),
+    ));
+    if let Some(recreate) = recreate {
+        entity.insert(recreate);
+    }
+    let entity = entity.id();
+    app.update();
+
+    // Release the instance behind the back of the AudioSource
+    event_instance
+        .release()
+        .expect("Failed to release instance");
+    app.world()
+        .resource::<FmodStudio>()
+        .flush_commands()
+        .expect("Failed to flush commands");
+    assert!(!event_instance.is_valid());
+
+    app.update();
+    Some(entity)
+}
+
+#[test]
+fn test_invalid_audio_source() {
+    let mut app = setup_app();
+    let Some(entity) = spawn_and_release(&mut app, None) else {
+        return;
+    };
+
+    assert!(app.world().get::<InvalidAudioSource>(entity).is_some());
+
+    // Despawning an invalid source must not fail
+    app.world_mut().entity_mut(entity).despawn();
+    app.update();
+}
+
+#[test]
+fn test_recreate_audio_source() {
+    let mut app = setup_app();
+    let Some(entity) = spawn_and_release(&mut app, Some(RecreateAudioSource::new(true))) else {
+        return;
+    };
+
+    // The event is still loaded, so the instance is recreated right away
+    assert!(app.world().get::<InvalidAudioSource>(entity).is_none());
+    let audio_source = app.world().get::<AudioSource>(entity).unwrap();
+    assert!(audio_source.is_valid());
+}
+
+#[test]
+fn test_audio_sources_are_validated_in_turn() {
+    let mut app = setup_app();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping invalid audio source test - no valid events in banks");
+        return;
+    };
+
+    let sources = (0..20)
+        .map(|_| {
+            let event_instance = event_description
+                .create_instance()
+                .expect("Failed to create instance");
+            let entity = app
+                .world_mut()
+                .spawn((
+                    AudioSource {
+                        event_instance,
+                        despawn_stop_mode: StopMode::Immediate,
+                    },
+                    Transform::default(),
+                ))
+                .id();
+            (entity, event_instance)
+        })
+        .collect::<Vec<_>>();
+    app.update();
+
+    for (_, event_instance) in &sources {
+        event_instance
+            .release()
+            .expect("Failed to release instance");
+    }
+    app.world()
+        .resource::<FmodStudio>()
+        .flush_commands()
+        .expect("Failed to flush commands");
+
+    let invalid_count = |app: &App| {
+        sources
+            .iter()
+            .filter(|(entity, _)| app.world().get::<InvalidAudioSource>(*entity).is_some())
+            .count()
+    };
+
+    // Only a few sources are checked per frame
+    app.update();
+    assert_eq!(invalid_count(&app), 8);
+
+    for _ in 0..2 {
+        app.update();
+    }
+    assert_eq!(invalid_count(&app), sources.len());
+}
+
+#[test]
+fn test_invalid_handle_error_validates_audio_source() {
+    let mut app = setup_app();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping invalid audio source test - no valid events in banks");
+        return;
+    };
+
+    for _ in 0..20 {
+        let event_instance = event_description
+            .create_instance()
+            .expect("Failed to create instance");
+        app.world_mut().spawn((
+            AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            },
+            Transform::default(),
+        ));
+    }
+
+    // The first frame checks the sources 0 to 7, the next ones 8 to 15 and then 16 to 19 and 0
+    // to 3, so the sixth source is only found through the error
+    app.update();
+    let (entity, event_instance) = app
+        .world_mut()
+        .query::<(Entity, &AudioSource)>()
+        .iter(app.world())
+        .map(|(entity, audio_source)| (entity, audio_source.event_instance))
+        .nth(5)
+        .unwrap();
+
+    event_instance
+        .release()
+        .expect("Failed to release instance");
+    app.world()
+        .resource::<FmodStudio>()
+        .flush_commands()
+        .expect("Failed to flush commands");
+
+    // Moving the source makes FMOD report the invalid handle
+    app.world_mut()
+        .get_mut::<Transform>(entity)
+        .unwrap()
+        .translation
+        .x = 1.0;
+    app.update();
+    assert!(app.world().get::<InvalidAudioSource>(entity).is_none());
+
+    app.update();
+    assert!(app.world().get::<InvalidAudioSource>(entity).is_some());
+}
@@ -0,0 +1,161 @@
+// Test the occlusion subsystem
+// Verifies occlusion values from a custom provider, throttling, ignored sources and missing
+// parameters
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_fmod::components::{AudioListener, AudioSource};
+use bevy_fmod::{
+    FmodErrorMessage, FmodPlugin, FmodStudio, IgnoreOcclusion, Occlusion, OcclusionOutput,
+    OcclusionPlugin, OcclusionProvider, OcclusionSettings,
+};
+use libfmod::StopMode;
+
+/// Occludes everything behind a wall at x = 5
+struct WallProvider;
+
+impl OcclusionProvider for WallProvider {
+    type Param = ();
+
+    fn occlusion(_: &(), listener: Vec3, source: Vec3, _: Entity) -> f32 {
+        if (listener.x < 5.0) != (source.x < 5.0) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+fn create_app(sources_per_frame: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(OcclusionPlugin::<WallProvider>::new(OcclusionSettings {
+            output: OcclusionOutput::ChannelGroup { reverb_factor: 0.5 },
+            smoothing: Duration::ZERO,
+            sources_per_frame,
+        }));
+    app
+}
+
+fn spawn_source(app: &mut App, x: f32) -> Option<Entity> {
+    let studio = app.world().resource::<FmodStudio>();
+    let event_instance = studio
+        .get_event("event:/test")
+        .ok()?
+        .create_instance()
+        .ok()?;
+
+    Some(
+        app.world_mut()
+            .spawn((
+                AudioSource {
+                    event_instance,
+                    despawn_stop_mode: StopMode::Immediate,
+                },
+                Transform::from_xyz(x, 0.0, 0.0),
+            ))
+            .id(),
+    )
+}
+
+#[test]
+fn test_occlusion_from_provider() {
+    let mut app = create_app(8);
+    app.world_mut().spawn((AudioListener, Transform::default()));
+
+    let (Some(visible), Some(hidden)) = (spawn_source(&mut app, 2.0), spawn_source(&mut app, 10.0))
+    else {
+        println!("Skipping occlusion test - no valid events in banks");
+        return;
+    };
+
+    app.update();
+
+    let occlusion = |entity| app.world().get::<Occlusion>(entity).unwrap().value();
+    assert_eq!(occlusion(visible), 0.0);
+    assert_eq!(occlusion(hidden), 1.0);
+}
+
+#[test]
+fn test_occlusion_throttling() {
+    let mut app = create_app(1);
+    app.world_mut().spawn((AudioListener, Transform::default()));
+
+    let (Some(first), Some(second)) = (spawn_source(&mut app, 10.0), spawn_source(&mut app, 20.0))
+    else {
+        println!("Skipping occlusion test - no valid events in banks");
+        return;
+    };
+
+    // Only one source is tested per frame
+    app.update();
+    let occluded = [first, second]
+        .iter()
+        .filter(|entity| app.world().get::<Occlusion>(**entity).unwrap().target() == 1.0)
+        .count();
+    assert_eq!(occluded, 1);
+
+    app.update();
+    let occluded = [first, second]
+        .iter()
+        .filter(|entity| app.world().get::<Occlusion>(**entity).unwrap().target() == 1.0)
+        .count();
+    assert_eq!(occluded, 2);
+}
+
+#[test]
+fn test_ignore_occlusion() {
+    let mut app = create_app(8);
+    app.world_mut().spawn((AudioListener, Transform::default()));
+
+    let Some(source) = spawn_source(&mut app, 10.0) else {
+        println!("Skipping occlusion test - no valid events in banks");
+        return;
+    };
+
+    app.update();
+    assert_eq!(app.world().get::<Occlusion>(source).unwrap().value(), 1.0);
+
+    app.world_mut().entity_mut(source).insert(IgnoreOcclusion);
+    app.update();
+
+    assert_eq!(app.world().get::<Occlusion>(source).unwrap().value(), 0.0);
+}
+
+#[test]
+fn test_occlusion_without_parameter() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(OcclusionPlugin::<WallProvider>::new(OcclusionSettings {
+            output: OcclusionOutput::Parameter("DoesNotExist"),
+            smoothing: Duration::from_millis(100),
+            sources_per_frame: 8,
+        }));
+    app.world_mut().spawn((AudioListener, Transform::default()));
+
+    let Some(source) = spawn_source(&mut app, 10.0) else {
+        println!("Skipping occlusion test - no valid events in banks");
+        return;
+    };
+
+    // The smoothed value changes every frame, but the missing parameter is not an error
+    for _ in 0..5 {
+        app.update();
+    }
+
+    assert_eq!(app.world().get::<Occlusion>(source).unwrap().target(), 1.0);
+
+    let messages = app.world().resource::<Messages<FmodErrorMessage>>();
+    assert!(messages.is_empty());
+}
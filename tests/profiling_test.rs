@@ -0,0 +1,119 @@
+// Test the FFI call counts
+// Verifies that calls into FMOD are counted per frame and per app
+
+use bevy::prelude::*;
+use bevy_fmod::components::{AudioListener, AudioSource};
+use bevy_fmod::{FmodFfiCalls, FmodPlugin, FmodStudio};
+use libfmod::StopMode;
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+    app
+}
+
+fn last_frame(app: &App) -> usize {
+    app.world().resource::<FmodFfiCalls>().last_frame
+}
+
+#[test]
+fn test_ffi_calls_per_frame() {
+    let mut app = create_app();
+    app.world_mut().spawn((AudioListener, Transform::default()));
+    app.update();
+
+    // Loading the banks is counted in the first frame
+    let first_frame = last_frame(&app);
+    app.update();
+    let one_listener = last_frame(&app);
+    assert!(first_frame > one_listener);
+
+    // Every additional listener sends its attributes and weight, after the number of listeners
+    // was changed once
+    app.world_mut().spawn((AudioListener, Transform::default()));
+    app.update();
+    assert_eq!(last_frame(&app), one_listener + 3);
+    app.update();
+    assert_eq!(last_frame(&app), one_listener + 2);
+}
+
+#[test]
+fn test_ffi_calls_are_counted_per_app() {
+    let mut quiet_app = create_app();
+    let mut busy_app = create_app();
+    for _ in 0..4 {
+        busy_app
+            .world_mut()
+            .spawn((AudioListener, Transform::default()));
+    }
+
+    for _ in 0..2 {
+        quiet_app.update();
+    }
+    let quiet_calls = last_frame(&quiet_app);
+
+    // Running the other app in between doesn't change the count
+    for _ in 0..2 {
+        busy_app.update();
+        quiet_app.update();
+        assert_eq!(last_frame(&quiet_app), quiet_calls);
+    }
+    assert!(last_frame(&busy_app) > quiet_calls);
+}
+
+#[test]
+fn test_moving_audio_sources_add_ffi_calls() {
+    let mut app = create_app();
+    app.world_mut().spawn((AudioListener, Transform::default()));
+
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping profiling test - no valid events in banks");
+        return;
+    };
+
+    let sources = (0..4)
+        .map(|_| {
+            let event_instance = event_description
+                .create_instance()
+                .expect("Failed to create instance");
+            app.world_mut()
+                .spawn((
+                    AudioSource {
+                        event_instance,
+                        despawn_stop_mode: StopMode::Immediate,
+                    },
+                    Transform::default(),
+                ))
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    for _ in 0..2 {
+        app.update();
+    }
+    let static_calls = last_frame(&app);
+
+    // Static sources cost the same every frame
+    app.update();
+    assert_eq!(last_frame(&app), static_calls);
+
+    // Every moved source sends its 3D attributes once
+    for &entity in &sources[..3] {
+        app.world_mut()
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .x += 1.0;
+    }
+    app.update();
+    assert_eq!(last_frame(&app), static_calls + 3);
+
+    app.update();
+    assert_eq!(last_frame(&app), static_calls);
+}
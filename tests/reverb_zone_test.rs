@@ -0,0 +1,58 @@
+// Test the ReverbZone component
+// Verifies reverb presets and the lifecycle of FMOD 3D reverbs
+
+use bevy::prelude::*;
+use bevy_fmod::FmodPlugin;
+use bevy_fmod::components::{ReverbPreset, ReverbZone};
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+    app
+}
+
+#[test]
+fn test_reverb_presets() {
+    let cave = ReverbPreset::Cave.properties();
+    assert_eq!(cave.decay_time, 2900.0);
+    assert_eq!(cave.high_cut, 20000.0);
+    assert_eq!(cave.wet_level, -11.3);
+
+    let off = ReverbPreset::Off.properties();
+    assert_eq!(off.wet_level, -80.0);
+}
+
+#[test]
+fn test_reverb_zone_lifecycle() {
+    let mut app = create_app();
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            ReverbZone::new(ReverbPreset::Hallway, 2.0, 10.0),
+            Transform::from_xyz(0.0, 0.0, -5.0),
+        ))
+        .id();
+
+    app.update();
+
+    let zone = app.world().get::<ReverbZone>(entity).unwrap();
+    assert!(zone.reverb().is_some());
+
+    // Changing the preset updates the existing reverb
+    let reverb = zone.reverb();
+    app.world_mut()
+        .get_mut::<ReverbZone>(entity)
+        .unwrap()
+        .properties = ReverbPreset::StoneCorridor.properties();
+    app.update();
+    assert_eq!(app.world().get::<ReverbZone>(entity).unwrap().reverb(), reverb);
+
+    app.world_mut().despawn(entity);
+    app.update();
+}
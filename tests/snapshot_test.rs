@@ -0,0 +1,98 @@
+// Test snapshot components and state integration
+// Verifies snapshot creation, intensity fades and state-bound activation
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy_fmod::components::Snapshot;
+use bevy_fmod::utilities::SnapshotOnState;
+use bevy_fmod::{FmodErrorMessage, FmodPlugin, FmodStudio};
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+enum GameState {
+    #[default]
+    Playing,
+    Paused,
+}
+
+#[test]
+fn test_snapshot_from_invalid_path() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(FmodPlugin::new(&[
+        "tests/data/Master.bank",
+        "tests/data/Master.strings.bank",
+    ]));
+
+    let studio = app.world().resource::<FmodStudio>();
+    assert!(Snapshot::from_path(studio, "snapshot:/NonExistent").is_err());
+}
+
+#[test]
+fn test_snapshot_missing_intensity_parameter_reports_error() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(FmodPlugin::new(&[
+        "tests/data/Master.bank",
+        "tests/data/Master.strings.bank",
+    ]));
+
+    let studio = app.world().resource::<FmodStudio>();
+
+    // event:/test has no "Intensity" parameter, so the fade is tracked but FMOD reports an error
+    match studio.get_event("event:/test") {
+        Ok(event_desc) => {
+            let mut snapshot = Snapshot::new(event_desc)
+                .expect("Failed to create snapshot")
+                .with_intensity(0.0);
+            assert_eq!(snapshot.intensity(), 0.0);
+
+            snapshot.fade_to(Snapshot::FULL_INTENSITY, Duration::ZERO);
+            let entity = app.world_mut().spawn(snapshot).id();
+            app.update();
+
+            let snapshot = app.world().get::<Snapshot>(entity).unwrap();
+            assert_eq!(snapshot.intensity(), Snapshot::FULL_INTENSITY);
+
+            let messages = app.world().resource::<Messages<FmodErrorMessage>>();
+            let mut cursor = messages.get_cursor();
+            let error = cursor
+                .read(messages)
+                .find(|message| message.entity == Some(entity))
+                .expect("Expected an error for the missing parameter");
+            assert!(error.code.is_some());
+
+            app.world_mut().despawn(entity);
+        }
+        Err(_) => {
+            println!("Skipping snapshot parameter test - no valid events in banks");
+        }
+    }
+}
+
+#[test]
+fn test_snapshot_on_state_plugin() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(StatesPlugin)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .init_state::<GameState>()
+        .add_plugins(SnapshotOnState::new(GameState::Paused, "snapshot:/NonExistent"));
+
+    app.update();
+
+    // A missing snapshot is reported, not a panic
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Paused);
+    app.update();
+
+    let mut snapshots = app.world_mut().query::<&Snapshot>();
+    assert_eq!(snapshots.iter(app.world()).count(), 0);
+
+    let messages = app.world().resource::<Messages<FmodErrorMessage>>();
+    let mut cursor = messages.get_cursor();
+    assert_eq!(cursor.read(messages).count(), 1);
+}
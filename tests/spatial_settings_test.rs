@@ -0,0 +1,93 @@
+// Test spatial settings
+// Verifies axis mappings and that 3D settings are applied to the core system
+
+use bevy::prelude::*;
+use bevy_fmod::{AxisMapping, FmodPlugin, FmodStudio, SpatialMode, SpatialSettings};
+
+#[test]
+fn test_axis_mapping_z_up() {
+    let axes = AxisMapping::Z_UP;
+
+    // World up (Z) becomes FMOD up (Y)
+    assert_eq!(axes.map_direction(Vec3::Z), Vec3::Y);
+    // World forward (Y) becomes FMOD forward (-Z)
+    assert_eq!(axes.map_direction(Vec3::Y), Vec3::NEG_Z);
+    assert_eq!(axes.map_vector(Vec3::X), Vec3::X);
+}
+
+#[test]
+fn test_axis_mapping_scale() {
+    let axes = AxisMapping::IDENTITY.with_scale(0.01);
+
+    assert_eq!(axes.map_vector(Vec3::new(100.0, 0.0, 0.0)), Vec3::new(1.0, 0.0, 0.0));
+    // Directions stay normalized
+    assert_eq!(axes.map_direction(Vec3::Y), Vec3::Y);
+}
+
+#[test]
+fn test_spatial_settings_applied() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(SpatialSettings {
+            doppler_scale: 0.5,
+            distance_factor: 2.0,
+            rolloff_scale: 1.5,
+            ..SpatialSettings::default()
+        })
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let core = studio.get_core_system().expect("Failed to get core system");
+    let (doppler_scale, distance_factor, rolloff_scale) =
+        core.get_3d_settings().expect("Failed to get 3D settings");
+
+    assert_eq!(doppler_scale, 0.5);
+    assert_eq!(distance_factor, 2.0);
+    assert_eq!(rolloff_scale, 1.5);
+}
+
+#[test]
+fn test_side_scroller_mode() {
+    let settings = SpatialSettings {
+        mode: SpatialMode::SideScroller {
+            pixels_per_meter: 100.0,
+        },
+        ..SpatialSettings::default()
+    };
+
+    // Z is layer depth and ignored, pixels are converted to meters
+    assert_eq!(
+        settings.map_vector(Vec3::new(200.0, 50.0, 10.0)),
+        Vec3::new(2.0, 0.5, 0.0)
+    );
+    // Orientation is fixed, regardless of the entity's rotation
+    assert_eq!(
+        settings.map_orientation(Vec3::X, Vec3::Z),
+        (Vec3::NEG_Z, Vec3::Y)
+    );
+}
+
+#[test]
+fn test_top_down_mode() {
+    let settings = SpatialSettings {
+        mode: SpatialMode::TopDown {
+            pixels_per_meter: 10.0,
+        },
+        ..SpatialSettings::default()
+    };
+
+    // The top of the screen is in front of the listener
+    assert_eq!(
+        settings.map_vector(Vec3::new(10.0, 20.0, 5.0)),
+        Vec3::new(1.0, 0.0, -2.0)
+    );
+    assert_eq!(
+        settings.map_orientation(Vec3::NEG_Z, Vec3::Y),
+        (Vec3::NEG_Z, Vec3::Y)
+    );
+}
@@ -4,7 +4,9 @@
 use bevy::prelude::*;
 use bevy::window::{PrimaryWindow, Window, WindowPlugin};
 use bevy_fmod::{FmodPlugin, FmodStudio};
-use bevy_fmod::utilities::MuteWhenUnfocused;
+use bevy_fmod::components::AudioSource;
+use bevy_fmod::utilities::{IgnoreVirtualTime, MuteWhenUnfocused, PauseWithVirtualTime};
+use libfmod::StopMode;
 
 #[test]
 fn test_mute_when_unfocused_plugin_initialization() {
@@ -77,3 +79,147 @@ fn test_mute_utility_without_window_plugin() {
 
     println!("FMOD works without mute utility");
 }
+
+#[test]
+fn test_pause_with_virtual_time_pauses_bus() {
+    // Test that pausing virtual time pauses the configured bus
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(PauseWithVirtualTime::bus("bus:/"));
+
+    app.update();
+
+    app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let master_bus = studio.get_bus("bus:/").expect("Master bus should exist");
+    assert!(master_bus.get_paused().expect("Should be able to get paused state"));
+
+    app.world_mut().resource_mut::<Time<Virtual>>().unpause();
+    app.update();
+
+    let studio = app.world().resource::<FmodStudio>();
+    let master_bus = studio.get_bus("bus:/").expect("Master bus should exist");
+    assert!(!master_bus.get_paused().expect("Should be able to get paused state"));
+
+    println!("Master bus follows virtual time pause state");
+}
+
+fn spawn_started_source(app: &mut App) -> Option<Entity> {
+    let studio = app.world().resource::<FmodStudio>();
+    let Ok(event_description) = studio.get_event("event:/test") else {
+        println!("Skipping virtual time test - no valid events in banks");
+        return None;
+    };
+    let event_instance = event_description
+        .create_instance()
+        .expect("Failed to create instance");
+    event_instance.start().expect("Failed to start instance");
+
+    Some(
+        app.world_mut()
+            .spawn(AudioSource {
+                event_instance,
+                despawn_stop_mode: StopMode::Immediate,
+            })
+            .id(),
+    )
+}
+
+fn is_paused(app: &App, entity: Entity) -> bool {
+    app.world()
+        .get::<AudioSource>(entity)
+        .expect("Entity should have an AudioSource")
+        .get_paused()
+        .expect("Should be able to get paused state")
+}
+
+#[test]
+fn test_pause_with_virtual_time_pauses_audio_sources() {
+    // Test that pausing virtual time pauses sources, including ones started during the pause
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(PauseWithVirtualTime::audio_sources());
+
+    app.update();
+
+    let Some(before) = spawn_started_source(&mut app) else {
+        return;
+    };
+    let ignored = spawn_started_source(&mut app).unwrap();
+    app.world_mut()
+        .entity_mut(ignored)
+        .insert(IgnoreVirtualTime);
+    app.update();
+
+    app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    app.update();
+    assert!(is_paused(&app, before));
+    assert!(
+        !is_paused(&app, ignored),
+        "Ignored sources should keep playing"
+    );
+
+    let during = spawn_started_source(&mut app).unwrap();
+    app.update();
+    assert!(
+        is_paused(&app, during),
+        "Sources started during the pause should be paused"
+    );
+
+    app.world_mut().resource_mut::<Time<Virtual>>().unpause();
+    app.update();
+    assert!(!is_paused(&app, before));
+    assert!(!is_paused(&app, during));
+}
+
+#[test]
+fn test_pause_with_virtual_time_scales_pitch() {
+    // Test that the pitch of sources follows the relative speed of virtual time
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]))
+        .add_plugins(PauseWithVirtualTime::audio_sources().with_pitch_scaling());
+
+    app.update();
+
+    let Some(entity) = spawn_started_source(&mut app) else {
+        return;
+    };
+    app.world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(0.5);
+    app.update();
+
+    let (pitch, _) = app
+        .world()
+        .get::<AudioSource>(entity)
+        .unwrap()
+        .get_pitch()
+        .expect("Should be able to get pitch");
+    assert_eq!(pitch, 0.5);
+
+    // Sources spawned later get the current speed as well
+    let later = spawn_started_source(&mut app).unwrap();
+    app.update();
+
+    let (pitch, _) = app
+        .world()
+        .get::<AudioSource>(later)
+        .unwrap()
+        .get_pitch()
+        .expect("Should be able to get pitch");
+    assert_eq!(pitch, 0.5);
+}
@@ -0,0 +1,206 @@
+// Test the Velocity component
+// Verifies manual velocities, physics sources, smoothing and teleport detection
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_fmod::components::{Velocity, VelocityFromComponent};
+use bevy_fmod::{AxisMapping, FmodPlugin, SpatialMode, SpatialSettings};
+
+#[derive(Component)]
+struct PhysicsVelocity(Vec3);
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .add_plugins(FmodPlugin::new(&[
+            "tests/data/Master.bank",
+            "tests/data/Master.strings.bank",
+        ]));
+    app
+}
+
+#[test]
+fn test_manual_velocity() {
+    let mut app = create_app();
+
+    let entity = app
+        .world_mut()
+        .spawn((Velocity::manual(Vec3::new(5.0, 0.0, 0.0)), Transform::default()))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert_eq!(velocity.current(), Vec3::new(5.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_velocity_from_component() {
+    let mut app = create_app();
+    app.add_plugins(VelocityFromComponent::<PhysicsVelocity>::new(|v| v.0));
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            Velocity::default(),
+            PhysicsVelocity(Vec3::new(0.0, -9.81, 0.0)),
+            Transform::default(),
+        ))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert_eq!(velocity.current(), Vec3::new(0.0, -9.81, 0.0));
+}
+
+#[test]
+fn test_velocity_teleport_reset() {
+    let mut app = create_app();
+
+    // Teleport detection is enabled by default
+    let entity = app
+        .world_mut()
+        .spawn((Velocity::default(), Transform::default()))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    app.world_mut().get_mut::<Transform>(entity).unwrap().translation = Vec3::splat(1000.0);
+
+    for _ in 0..3 {
+        app.update();
+
+        // Without teleport detection this would be a velocity of over 17000 units per second
+        let velocity = app.world().get::<Velocity>(entity).unwrap();
+        assert_eq!(velocity.current(), Vec3::ZERO);
+    }
+}
+
+#[test]
+fn test_velocity_teleport_speed() {
+    let mut app = create_app();
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            Velocity::default().with_teleport_speed(100.0),
+            Transform::default(),
+        ))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // 5 units in 100 ms are 50 units per second, below the threshold
+    app.world_mut().get_mut::<Transform>(entity).unwrap().translation.x += 5.0;
+    app.update();
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert!((velocity.current().x - 50.0).abs() < 1e-3);
+
+    // 20 units in 100 ms are 200 units per second, above the threshold
+    app.world_mut().get_mut::<Transform>(entity).unwrap().translation.x += 20.0;
+    app.update();
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert_eq!(velocity.current(), Vec3::ZERO);
+}
+
+#[test]
+fn test_velocity_teleport_speed_in_fmod_space() {
+    let mut app = create_app();
+    app.insert_resource(SpatialSettings {
+        axes: AxisMapping::IDENTITY.with_scale(0.01),
+        ..SpatialSettings::default()
+    });
+
+    let entity = app
+        .world_mut()
+        .spawn((Velocity::default(), Transform::default()))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // 200 centimeters in 100 ms are 20 meters per second, far below the default threshold
+    app.world_mut().get_mut::<Transform>(entity).unwrap().translation.x += 200.0;
+    app.update();
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert!((velocity.current().x - 2000.0).abs() < 1e-1);
+
+    // 200 meters in 100 ms are 2000 meters per second, above it
+    app.world_mut().get_mut::<Transform>(entity).unwrap().translation.x += 20000.0;
+    app.update();
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert_eq!(velocity.current(), Vec3::ZERO);
+}
+
+#[test]
+fn test_velocity_teleport_speed_in_pixels() {
+    let mut app = create_app();
+    app.insert_resource(SpatialSettings {
+        mode: SpatialMode::SideScroller {
+            pixels_per_meter: 100.0,
+        },
+        ..SpatialSettings::default()
+    });
+
+    let entity = app
+        .world_mut()
+        .spawn((Velocity::default(), Transform::default()))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // 300 pixels in 100 ms are 30 meters per second
+    app.world_mut().get_mut::<Transform>(entity).unwrap().translation.x += 300.0;
+    app.update();
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert!((velocity.current().x - 3000.0).abs() < 1e-1);
+}
+
+#[test]
+fn test_velocity_smoothing() {
+    let mut app = create_app();
+
+    let entity = app
+        .world_mut()
+        .spawn((Velocity::default().with_smoothing(0.1), Transform::default()))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // Move at a constant 10 units per second
+    let mut previous = 0.0;
+    for frame in 0..20 {
+        app.world_mut().get_mut::<Transform>(entity).unwrap().translation.x += 1.0;
+        app.update();
+
+        let current = app.world().get::<Velocity>(entity).unwrap().current().x;
+        if frame == 0 {
+            // With a 100 ms frame and 100 ms smoothing, the velocity moves 1 - 1/e of the way
+            let expected = 10.0 * (1.0 - (-1.0f32).exp());
+            assert!((current - expected).abs() < 1e-3, "Got {current}");
+        }
+        assert!(current >= previous);
+        assert!(current <= 10.0 + 1e-3);
+        previous = current;
+    }
+
+    assert!((previous - 10.0).abs() < 1e-3);
+}